@@ -0,0 +1,54 @@
+// `cargo bench` runs these locally; they aren't part of CI. The server list comes from the bundled
+// fixture, a page-shaped response of a little over a thousand servers (two of them malformed).
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use squad_roulette::animation::{row_at, server_at, visible_rows};
+use squad_roulette::api::parse_page;
+use squad_roulette::audio::{synthesize_click, CASINO_CLICK, MECHANICAL_CLICK};
+use squad_roulette::model::{apply_filters, FilterSettings, ServerItem};
+use squad_roulette::snapshot;
+
+const FIXTURE: &[u8] = include_bytes!("fixtures/servers_page.json");
+
+fn fixture_servers() -> Vec<ServerItem> {
+    parse_page(FIXTURE).expect("the fixture parses").servers
+}
+
+fn page_parsing(c: &mut Criterion) {
+    c.bench_function("parse_page 1 MB fixture", |b| b.iter(|| parse_page(black_box(FIXTURE)).unwrap()));
+}
+
+fn filtering(c: &mut Criterion) {
+    let servers: Vec<ServerItem> = fixture_servers().into_iter().take(1000).collect();
+    let filters = FilterSettings::default();
+    c.bench_function("apply_filters 1k servers", |b| b.iter(|| apply_filters(black_box(&servers), black_box(&filters))));
+}
+
+// What the wheel works out every frame of a spin: the rows in view and the server under the pointer.
+fn winner_index(c: &mut Criterion) {
+    let pitch = 60.0;
+    let scrolls: Vec<f32> = (0..1000).map(|i| i as f32 * 37.3 - 5000.0).collect();
+    c.bench_function("winner index over 1k scroll positions", |b| {
+        b.iter(|| scrolls.iter().map(|&scroll| server_at(row_at(black_box(scroll), pitch), 1000)).sum::<usize>())
+    });
+    c.bench_function("visible rows over 1k scroll positions", |b| {
+        b.iter(|| scrolls.iter().map(|&scroll| visible_rows(black_box(scroll), 600.0, pitch).count()).sum::<usize>())
+    });
+}
+
+// Startup reads the server cache before the first frame; the same list as JSON shows what the binary layout saves.
+fn cache_loading(c: &mut Criterion) {
+    let servers = fixture_servers();
+    let json = serde_json::to_vec(&servers).unwrap();
+    let binary = snapshot::encode(&servers, 0);
+    c.bench_function("load server cache from JSON", |b| b.iter(|| serde_json::from_slice::<Vec<ServerItem>>(black_box(&json)).unwrap()));
+    c.bench_function("load server cache from snapshot", |b| b.iter(|| snapshot::decode(black_box(&binary)).unwrap().servers));
+}
+
+fn click_synthesis(c: &mut Criterion) {
+    c.bench_function("synthesize mechanical click", |b| b.iter(|| synthesize_click(black_box(&MECHANICAL_CLICK))));
+    c.bench_function("synthesize casino click", |b| b.iter(|| synthesize_click(black_box(&CASINO_CLICK))));
+}
+
+criterion_group!(benches, page_parsing, filtering, winner_index, cache_loading, click_synthesis);
+criterion_main!(benches);
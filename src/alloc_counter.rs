@@ -0,0 +1,41 @@
+// Development aid: built with `--features alloc-counter`, every heap allocation is counted so the frame
+// counter can show allocations per second next to the frame rate. Without the feature nothing is wrapped
+// and `allocations` has nothing to report.
+
+#[cfg(feature = "alloc-counter")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    pub static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+    struct Counting;
+
+    // SAFETY: every call is forwarded unchanged to the system allocator; only a counter is bumped.
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+}
+
+// Allocations made since the start, or None when the counter isn't compiled in.
+pub fn allocations() -> Option<u64> {
+    #[cfg(feature = "alloc-counter")]
+    return Some(counting::ALLOCATIONS.load(std::sync::atomic::Ordering::Relaxed));
+    #[cfg(not(feature = "alloc-counter"))]
+    None
+}
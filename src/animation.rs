@@ -0,0 +1,418 @@
+use serde::{Deserialize, Serialize};
+
+use crate::selection::SpinOutcome;
+
+const BRAKING_POWER: i32 = 7;
+// Kept small on purpose: the overshoot is a fraction of the whole spin distance, so even 1% is several rows.
+const BACK_OVERSHOOT: f32 = 0.5;
+const SETTLE_STIFFNESS: f32 = 320.0;
+// Slightly under-damped (ratio ~0.34), so the wheel rocks two or three times before resting.
+const SETTLE_DAMPING: f32 = 12.0;
+const SETTLE_STEP: f32 = 1.0 / 240.0;
+const SETTLE_REST_DISTANCE: f32 = 0.05;
+const SETTLE_REST_SPEED: f32 = 1.0;
+const NEAR_MISS_OUT_TIME: f32 = 0.3;
+const NEAR_MISS_BACK_TIME: f32 = 0.4;
+// Pause after each of the final row-by-row steps; the last three are the slow "tick… tick… tick".
+const TICK_PAUSES: [f32; 5] = [0.08, 0.12, 0.2, 0.35, 0.6];
+const TICK_MOVE_TIME: f32 = 0.09;
+// The steps never take more than this share of a spin, so short spins squeeze them instead of running long.
+const TICK_SHARE: f32 = 0.4;
+const NUDGE_PAUSE: f32 = 0.7;
+const NUDGE_MOVE_TIME: f32 = 0.15;
+const NEAR_MISS_OVERSHOOT: f32 = 0.6;
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum Easing {
+    CubicOut,
+    QuintOut,
+    ExpoOut,
+    #[default]
+    Power7,
+    BackOut,
+}
+
+impl Easing {
+    pub const ALL: [Easing; 5] = [Easing::CubicOut, Easing::QuintOut, Easing::ExpoOut, Easing::Power7, Easing::BackOut];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Easing::CubicOut => "Cubic",
+            Easing::QuintOut => "Quint",
+            Easing::ExpoOut => "Expo",
+            Easing::Power7 => "Power 7",
+            Easing::BackOut => "Back",
+        }
+    }
+
+    /// Eased progress for linear progress `t`, clamped to 0..=1. Starts at 0 and ends at 1; BackOut
+    /// passes 1 on the way.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::CubicOut => power_out(t, 3),
+            Easing::QuintOut => power_out(t, 5),
+            Easing::ExpoOut => expo_out(t),
+            Easing::Power7 => power_out(t, BRAKING_POWER),
+            Easing::BackOut => back_out(t),
+        }
+    }
+
+    pub fn overshoots(self) -> bool {
+        self == Easing::BackOut
+    }
+}
+
+/// Decelerates like `1 - (1 - t)^power`; the higher the power, the longer the slow tail.
+pub(crate) fn power_out(t: f32, power: i32) -> f32 {
+    1.0 - (1.0 - t).powi(power)
+}
+
+pub(crate) fn expo_out(t: f32) -> f32 {
+    if t >= 1.0 { 1.0 } else { 1.0 - 2f32.powf(-10.0 * t) }
+}
+
+pub(crate) fn back_out(t: f32) -> f32 {
+    let u = t - 1.0;
+    1.0 + (BACK_OVERSHOOT + 1.0) * u.powi(3) + BACK_OVERSHOOT * u.powi(2)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpinPhase {
+    Main,
+    Ticks,
+    Settle,
+    NearMissOut,
+    NearMissBack,
+    Nudge,
+    Done,
+}
+
+#[derive(Clone, Debug)]
+pub struct SpinAnimation {
+    start: f32,
+    target: f32,
+    duration: f32,
+    easing: Easing,
+    landing: f32,
+    tick_pitch: f32,
+    tick_pauses: Vec<f32>,
+    tick: usize,
+    near_miss: Option<f32>,
+    nudge: f32,
+    phase: SpinPhase,
+    phase_time: f32,
+    position: f32,
+    velocity: f32,
+}
+
+impl SpinAnimation {
+    pub fn new(start: f32, target: f32, duration: f32, easing: Easing) -> Self {
+        Self { start, target, duration: duration.max(0.001), easing, landing: 0.0, tick_pitch: 0.0, tick_pauses: Vec::new(), tick: 0, near_miss: None, nudge: 0.0, phase: SpinPhase::Main, phase_time: 0.0, position: start, velocity: 0.0 }
+    }
+
+    // The main phase lands `offset` away from the target within the winner's row; the finish then recentres it.
+    pub fn landing_offset(mut self, offset: f32) -> Self {
+        self.landing = offset;
+        self
+    }
+
+    // Stops the main phase a few rows short and steps the rest one `pitch` at a time, each pause longer than
+    // the last. The steps come out of the same duration, and only as many as the distance has rows.
+    pub fn with_ticks(mut self, pitch: f32) -> Self {
+        let rows = ((self.rest() - self.start) / pitch).floor().max(0.0) as usize;
+        let pauses = &TICK_PAUSES[TICK_PAUSES.len() - rows.min(TICK_PAUSES.len())..];
+        let total: f32 = pauses.iter().sum();
+        let scale = if total > 0.0 { (self.duration * TICK_SHARE / total).min(1.0) } else { 1.0 };
+        self.tick_pauses = pauses.iter().map(|p| p * scale).collect();
+        self.tick_pitch = pitch;
+        self.duration -= total * scale;
+        self
+    }
+
+    // After the steps (or the main phase), slip from the landing spot to `distance` past the target, then tick back.
+    pub fn with_near_miss(mut self, distance: f32) -> Self {
+        self.near_miss = Some(distance);
+        self
+    }
+
+    // Everything before the last phase comes to rest `distance` short of the target; after a pause
+    // the wheel creeps the final row. Call before with_ticks so the steps count towards the right spot.
+    pub fn with_nudge(mut self, distance: f32) -> Self {
+        self.nudge = distance;
+        self
+    }
+
+    // Where the wheel appears to stop, a nudge short of the real target.
+    fn rest(&self) -> f32 {
+        self.target - self.nudge
+    }
+
+    pub fn start(&self) -> f32 {
+        self.start
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn phase(&self) -> SpinPhase {
+        self.phase
+    }
+
+    // Share of the distance covered so far, 0..=1; the near miss and settle may briefly read past 1.
+    pub fn progress(&self) -> f32 {
+        let distance = self.target - self.start;
+        if distance <= 0.0 { 1.0 } else { (self.position - self.start) / distance }
+    }
+
+    pub fn advance(&mut self, dt: f32) -> f32 {
+        self.phase_time += dt;
+        match self.phase {
+            SpinPhase::Main => {
+                let end = self.rest() + self.landing - self.tick_pauses.len() as f32 * self.tick_pitch;
+                let t = self.phase_time / self.duration;
+                self.position = self.start + (end - self.start) * self.easing.apply(t);
+                // The power curves crawl for the last few frames; an overshooting curve crosses `end` early, so only the clock may stop it.
+                let arrived = !self.easing.overshoots() && (end - self.position).abs() < 0.5;
+                if t >= 1.0 || arrived {
+                    self.position = end;
+                    self.enter(if self.tick_pauses.is_empty() { self.finale() } else { SpinPhase::Ticks });
+                }
+            }
+            SpinPhase::Ticks => {
+                // Each step glides one row, so it crosses exactly one row boundary and gets exactly one click.
+                let pause = self.tick_pauses[self.tick];
+                let from = self.rest() + self.landing - (self.tick_pauses.len() - self.tick) as f32 * self.tick_pitch;
+                let u = (self.phase_time / TICK_MOVE_TIME.min(pause * 0.5)).min(1.0);
+                self.position = from + self.tick_pitch * u * u * (3.0 - 2.0 * u);
+                if self.phase_time >= pause {
+                    self.tick += 1;
+                    self.phase_time = 0.0;
+                    if self.tick == self.tick_pauses.len() { self.enter(self.finale()); }
+                }
+            }
+            SpinPhase::Settle => {
+                let mut remaining = dt;
+                while remaining > 0.0 {
+                    let step = remaining.min(SETTLE_STEP);
+                    let accel = -SETTLE_STIFFNESS * (self.position - self.rest()) - SETTLE_DAMPING * self.velocity;
+                    self.velocity += accel * step;
+                    self.position += self.velocity * step;
+                    remaining -= step;
+                }
+                if (self.position - self.rest()).abs() < SETTLE_REST_DISTANCE && self.velocity.abs() < SETTLE_REST_SPEED { self.enter(self.after_finale()); }
+            }
+            SpinPhase::NearMissOut => {
+                let distance = self.near_miss.unwrap_or_default();
+                let from = self.rest() + self.landing;
+                let u = (self.phase_time / NEAR_MISS_OUT_TIME).min(1.0);
+                self.position = from + (self.rest() + distance - from) * power_out(u, 2);
+                if u >= 1.0 { self.enter(SpinPhase::NearMissBack); }
+            }
+            SpinPhase::NearMissBack => {
+                let distance = self.near_miss.unwrap_or_default();
+                let u = (self.phase_time / NEAR_MISS_BACK_TIME).min(1.0);
+                self.position = self.rest() + distance * (1.0 - u * u * (3.0 - 2.0 * u));
+                if u >= 1.0 { self.enter(self.after_finale()); }
+            }
+            SpinPhase::Nudge => {
+                let u = ((self.phase_time - NUDGE_PAUSE) / NUDGE_MOVE_TIME).clamp(0.0, 1.0);
+                self.position = self.rest() + self.nudge * u * u * (3.0 - 2.0 * u);
+                if u >= 1.0 { self.finish(); }
+            }
+            SpinPhase::Done => {}
+        }
+        self.position
+    }
+
+    // Rescales the clock so the rest of the main phase plays out in `remaining` seconds from the same
+    // point on the curve: the wheel keeps decelerating onto the same target instead of jumping there.
+    // The steps still to come are squeezed the same way. Does nothing once less than `remaining` is left,
+    // so repeated calls are harmless.
+    pub fn fast_forward(&mut self, remaining: f32) {
+        let ticks = if self.tick_pauses.is_empty() { 0.0 } else { remaining * TICK_SHARE };
+        self.squeeze_ticks(ticks);
+        let remaining = remaining - ticks;
+        if self.phase != SpinPhase::Main || self.duration - self.phase_time <= remaining { return; }
+        let t = self.phase_time / self.duration;
+        self.duration = remaining / (1.0 - t);
+        self.phase_time = t * self.duration;
+    }
+
+    fn squeeze_ticks(&mut self, budget: f32) {
+        let left: f32 = self.tick_pauses[self.tick..].iter().sum();
+        if left <= budget { return; }
+        let scale = budget / left;
+        self.tick_pauses[self.tick..].iter_mut().for_each(|p| *p *= scale);
+        if self.phase == SpinPhase::Ticks { self.phase_time *= scale; }
+    }
+
+    pub fn finish(&mut self) {
+        self.position = self.target;
+        self.velocity = 0.0;
+        self.phase = SpinPhase::Done;
+    }
+
+    fn finale(&self) -> SpinPhase {
+        if self.near_miss.is_some() { SpinPhase::NearMissOut } else if self.landing != 0.0 { SpinPhase::Settle } else { self.after_finale() }
+    }
+
+    fn after_finale(&self) -> SpinPhase {
+        if self.nudge != 0.0 { SpinPhase::Nudge } else { SpinPhase::Done }
+    }
+
+    fn enter(&mut self, phase: SpinPhase) {
+        self.phase = phase;
+        self.phase_time = 0.0;
+        self.velocity = 0.0;
+        if phase == SpinPhase::Done { self.finish(); }
+    }
+}
+
+// Hold times for the first and last name of a crossfade cycle; the ones between ramp up evenly.
+const CROSSFADE_FIRST_HOLD: f32 = 0.35;
+const CROSSFADE_LAST_HOLD: f32 = 0.65;
+// Share of each hold spent blending into the next name.
+const CROSSFADE_BLEND: f32 = 0.4;
+
+// The reduced-motion stand-in for a spin: nothing moves, names just fade into one another in place,
+// each held a little longer than the last, until the final one (the winner) is fully shown.
+#[derive(Clone, Debug)]
+pub struct Crossfade {
+    names: Vec<usize>,
+    step: usize,
+    time: f32,
+}
+
+impl Crossfade {
+    pub fn new(names: Vec<usize>) -> Self {
+        Self { names, step: 0, time: 0.0 }
+    }
+
+    fn hold(&self, step: usize) -> f32 {
+        let t = step as f32 / self.names.len().saturating_sub(2).max(1) as f32;
+        CROSSFADE_FIRST_HOLD + (CROSSFADE_LAST_HOLD - CROSSFADE_FIRST_HOLD) * t.min(1.0)
+    }
+
+    // Returns true when the next name has taken over, so the caller can tick once per name.
+    pub fn advance(&mut self, dt: f32) -> bool {
+        if self.done() { return false; }
+        self.time += dt;
+        if self.time < self.hold(self.step) { return false; }
+        self.step += 1;
+        self.time = 0.0;
+        true
+    }
+
+    pub fn current(&self) -> usize {
+        self.names[self.step.min(self.names.len() - 1)]
+    }
+
+    pub fn next(&self) -> Option<usize> {
+        self.names.get(self.step + 1).copied()
+    }
+
+    // How far the next name has faded in over the current one, 0..=1.
+    pub fn blend(&self) -> f32 {
+        let hold = self.hold(self.step);
+        let u = ((self.time / hold - (1.0 - CROSSFADE_BLEND)) / CROSSFADE_BLEND).clamp(0.0, 1.0);
+        u * u * (3.0 - 2.0 * u)
+    }
+
+    pub fn done(&self) -> bool {
+        self.step + 1 >= self.names.len()
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.step as f32 / self.names.len().saturating_sub(1).max(1) as f32
+    }
+
+    pub fn finish(&mut self) {
+        self.step = self.names.len().saturating_sub(1);
+        self.time = 0.0;
+    }
+}
+
+const TARGET_SCROLL_ROWS: usize = 100;
+const TARGET_SCROLL_SECS: f32 = 12.5;
+pub const SMALL_POOL_MAX: usize = 4;
+const SMALL_POOL_LOOPS: usize = 6;
+
+/// The server a virtual row shows. The wheel is endless: row `row` shows server `row mod len`,
+/// negative rows included, so the animation only ever moves through row numbers and never needs to
+/// know how often the list repeats.
+pub fn server_at(row: i64, len: usize) -> usize {
+    row.rem_euclid(len.max(1) as i64) as usize
+}
+
+/// The row under the pointer; the highlight, the passing name and the spin's start all round the same way.
+pub fn row_at(scroll: f32, pitch: f32) -> i64 {
+    (scroll / pitch).round() as i64
+}
+
+/// Rows at least partly inside a view `view` long whose middle is at `scroll`.
+pub fn visible_rows(scroll: f32, view: f32, pitch: f32) -> std::ops::Range<i64> {
+    let first = ((scroll - view / 2.0) / pitch - 0.5).floor() as i64;
+    let last = ((scroll + view / 2.0) / pitch + 0.5).ceil() as i64;
+    first..last
+}
+
+// Where a stopped wheel sits: the row under the pointer and the rows in view. Worked out when a frame
+// finds the wheel at rest and kept until it moves, is resized or changes layout, so an idle wheel
+// redraws from it without redoing the scroll maths.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RestingView {
+    pub scroll: f32,
+    view: f32,
+    pub pitch: f32,
+    pub row: i64,
+    pub rows: std::ops::Range<i64>,
+}
+
+impl RestingView {
+    pub fn new(scroll: f32, view: f32, pitch: f32) -> Self {
+        Self { scroll, view, pitch, row: row_at(scroll, pitch), rows: visible_rows(scroll, view, pitch) }
+    }
+
+    pub fn matches(&self, scroll: f32, view: f32, pitch: f32) -> bool {
+        self.scroll == scroll && self.view == view && self.pitch == pitch
+    }
+}
+
+/// How many row boundaries the pointer passed between two scroll positions, in either direction.
+/// A boundary sits half a pitch before each row, where the pointer hands over to the next item.
+pub fn rows_crossed(from: f32, to: f32, pitch: f32) -> u32 {
+    let row = |scroll: f32| ((scroll + pitch * 0.5) / pitch).floor() as i64;
+    row(to).abs_diff(row(from)) as u32
+}
+
+/// Rows a spin of `duration` seconds scrolls through before landing, never fewer than ten.
+// TARGET_SCROLL_ROWS is tuned for a TARGET_SCROLL_SECS spin; keep the same pace for shorter or longer ones.
+pub fn spin_rows(duration: f32) -> usize {
+    ((TARGET_SCROLL_ROWS as f32 * duration / TARGET_SCROLL_SECS).round() as usize).max(10)
+}
+
+/// The scroll a spin plays back for a pool of `pool_len` rows `pitch` apart. It starts from the outcome's
+/// start slot and always comes to rest on the winner's row; every phase in between is cosmetic.
+pub fn plan_spin(outcome: &SpinOutcome, pool_len: usize, pitch: f32) -> SpinAnimation {
+    let pool_len = pool_len.max(1);
+    let mut loops = (spin_rows(outcome.duration) / pool_len).max(1);
+    // A couple of rows repeated a hundred times is just a strobe; travel less so the motion stays readable.
+    if pool_len <= SMALL_POOL_MAX { loops = loops.min(SMALL_POOL_LOOPS); }
+
+    // Carry on from where the wheel rests, less whole pool lengths so the position stays bounded,
+    // then travel full loops plus the gap to the winner's row.
+    let scroll = outcome.start_slot * pitch;
+    let resting_row = row_at(scroll, pitch);
+    let residual = scroll - resting_row as f32 * pitch;
+    let resting = server_at(resting_row, pool_len);
+    let delta = (outcome.winner_idx % pool_len + pool_len - resting) % pool_len;
+    let target = (resting + loops * pool_len + delta) as f32 * pitch;
+    let start = resting as f32 * pitch + residual;
+
+    let spin = SpinAnimation::new(start, target, outcome.duration, outcome.easing).landing_offset(outcome.offset * pitch);
+    let spin = if outcome.nudge { spin.with_nudge(pitch) } else { spin };
+    let spin = spin.with_ticks(pitch);
+    if outcome.near_miss { spin.with_near_miss(NEAR_MISS_OVERSHOOT * pitch) } else { spin }
+}
@@ -0,0 +1,226 @@
+use std::sync::mpsc::Sender;
+
+use reqwest::blocking::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::model::ServerItem;
+use crate::worker::Ticket;
+
+const MAX_PAGES: u32 = 5; 
+const PAYLOAD_SNIPPET_BYTES: usize = 512;
+
+#[derive(Deserialize, Debug, Clone)]
+struct ApiAttributes {
+    name: String,
+    players: u32,
+    #[serde(rename = "maxPlayers")]
+    max_players: u32,
+    details: ApiDetails,
+    country: Option<String>,
+    ip: Option<String>,
+    port: Option<u32>,
+    rank: Option<u32>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ApiDetails {
+    map: Option<String>,
+    #[serde(rename = "gameMode")]
+    game_mode: Option<String>,
+    #[serde(rename = "squad_publicQueue")]
+    public_queue: Option<u32>,
+    #[serde(rename = "licensedServer")]
+    licensed_server: Option<bool>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ApiServerData {
+    id: String,
+    attributes: ApiAttributes,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ApiLinks {
+    next: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct ApiResponse {
+    data: Vec<Lenient<ApiServerData>>,
+    links: Option<ApiLinks>,
+}
+
+// A list entry that's skipped rather than fatal when it doesn't have the expected shape, so one odd
+// server can't throw away the other 99 on its page.
+#[derive(Debug, Clone)]
+struct Lenient<T>(Option<T>);
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Lenient<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        Ok(Lenient(serde_json::from_value(value).ok()))
+    }
+}
+
+/// One page of the server list, with the entries that didn't have the expected shape left out and counted.
+pub struct Page {
+    pub servers: Vec<ServerItem>,
+    pub skipped: usize,
+    pub next: Option<String>,
+}
+
+/// Parses one BattleMetrics server-list page straight from the body as it's read; nothing is buffered
+/// beyond what the reader holds. Only a body that isn't a page at all is an error.
+pub fn parse_page(body: impl std::io::Read) -> serde_json::Result<Page> {
+    let json: ApiResponse = serde_json::from_reader(body)?;
+    let entries = json.data.len();
+    let servers: Vec<ServerItem> = json.data.into_iter().filter_map(|entry| entry.0).map(|server_data| {
+        let attr = server_data.attributes;
+        ServerItem {
+            id: server_data.id,
+            name: attr.name,
+            players: attr.players,
+            max_players: attr.max_players,
+            map: attr.details.map.unwrap_or("Unknown".to_string()),
+            mode: attr.details.game_mode.unwrap_or("Unknown".to_string()),
+            country: attr.country.unwrap_or("??".to_string()),
+            queue: attr.details.public_queue.unwrap_or(0),
+            ip: attr.ip.unwrap_or_default(),
+            port: attr.port.unwrap_or(0),
+            rank: attr.rank,
+            licensed: attr.details.licensed_server.unwrap_or(false),
+        }
+    }).collect();
+    Ok(Page { skipped: entries - servers.len(), servers, next: json.links.and_then(|l| l.next) })
+}
+
+// Hands a response body to the JSON parser as it arrives while keeping its first bytes, so a page that
+// fails to parse can still be quoted in the log without buffering the whole body.
+struct SnippetReader<R> {
+    inner: R,
+    snippet: Vec<u8>,
+}
+
+impl<R> SnippetReader<R> {
+    fn new(inner: R) -> Self {
+        Self { inner, snippet: Vec::with_capacity(PAYLOAD_SNIPPET_BYTES) }
+    }
+
+    fn snippet(&self) -> String {
+        String::from_utf8_lossy(&self.snippet).into_owned()
+    }
+}
+
+impl<R: std::io::Read> std::io::Read for SnippetReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        let room = PAYLOAD_SNIPPET_BYTES.saturating_sub(self.snippet.len());
+        self.snippet.extend_from_slice(&buf[..read.min(room)]);
+        Ok(read)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FetchProgress {
+    pub pages_done: u32,
+}
+
+impl FetchProgress {
+    // How far through the most pages a refresh will fetch, for the progress bar.
+    pub fn fraction(self) -> f32 {
+        self.pages_done as f32 / MAX_PAGES as f32
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FetchSummary {
+    pub total_seen: usize,
+    // Counted against the current filters whenever they're applied, not by the fetch itself.
+    pub excluded_region: usize,
+    pub excluded_players: usize,
+    // Pages were left unfetched (MAX_PAGES, or a newer refresh took over), so the quietest servers are missing.
+    pub truncated: bool,
+    pub failed: bool,
+    pub partial: bool,
+    pub rate_limited: bool,
+    pub http_status: Option<u16>,
+}
+
+pub enum FetchMessage {
+    Progress(FetchProgress),
+    Page(Vec<ServerItem>),
+    Done(Vec<ServerItem>, FetchSummary),
+}
+
+pub fn fetch_roulette_servers(
+    tx: Sender<FetchMessage>, 
+    ticket: &Ticket,
+) {
+    let client = Client::new();
+    let mut all_servers = Vec::new();
+    let base_url = "https://api.battlemetrics.com/servers";
+    let mut next_url = base_url.to_string();
+    
+    let mut pages_fetched = 0;
+    let mut summary = FetchSummary::default();
+
+    let filters = [
+        ("filter[game]", "squad"),
+        ("filter[status]", "online"),
+        ("page[size]", "100"),
+        ("sort", "-players"),
+    ];
+
+    // A newer refresh makes this one pointless, so it stops at the next page boundary.
+    while !next_url.is_empty() && pages_fetched < MAX_PAGES && ticket.is_current() {
+        pages_fetched += 1;
+        let mut request = client.get(&next_url);
+        
+        if next_url == base_url {
+            request = request.query(&filters);
+        }
+
+        let _ = tx.send(FetchMessage::Progress(FetchProgress { pages_done: pages_fetched - 1 }));
+        match request.send() {
+            Ok(resp) => { 
+                if resp.status().is_success() {
+                    let status = resp.status();
+                    let mut body = SnippetReader::new(std::io::BufReader::new(resp));
+                    match parse_page(&mut body) {
+                        Ok(page) => {
+                            next_url = page.next.unwrap_or_default();
+                            if page.skipped > 0 { warn!("page {}: skipped {} malformed server entries", pages_fetched, page.skipped); }
+                            summary.total_seen += page.servers.len();
+                            if !page.servers.is_empty() { let _ = tx.send(FetchMessage::Page(page.servers.clone())); }
+                            all_servers.extend(page.servers);
+                        }
+                        Err(e) => {
+                            warn!("failed to parse page {} (HTTP {}): {}; body starts with: {}", pages_fetched, status, e, body.snippet());
+                            summary.failed = all_servers.is_empty();
+                            summary.partial = !summary.failed;
+                            next_url = String::new();
+                        }
+                    }
+                } else {
+                    warn!("page {} request failed with HTTP {}", pages_fetched, resp.status());
+                    summary.failed = all_servers.is_empty();
+                    summary.partial = !summary.failed;
+                    summary.rate_limited = resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS;
+                    summary.http_status = Some(resp.status().as_u16());
+                    next_url = String::new();
+                }
+            },
+            Err(e) => {
+                warn!("page {} request failed: {}", pages_fetched, e);
+                summary.failed = all_servers.is_empty();
+                summary.partial = !summary.failed;
+                next_url = String::new();
+            }
+        }
+    }
+    summary.truncated = !next_url.is_empty();
+    info!("fetched {} servers in {} pages{}", all_servers.len(), pages_fetched, if summary.truncated { ", more left unfetched" } else { "" });
+    let _ = tx.send(FetchMessage::Done(all_servers, summary));
+}
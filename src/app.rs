@@ -0,0 +1,1211 @@
+use std::collections::VecDeque;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use rand::Rng;
+use tracing::{info, warn, Level};
+
+use squad_roulette::animation::{
+    plan_spin, row_at, rows_crossed, server_at, visible_rows, Crossfade, RestingView, SpinAnimation, SpinPhase,
+};
+use squad_roulette::api::{fetch_roulette_servers, FetchMessage, FetchProgress, FetchSummary};
+use squad_roulette::audio::{load_sound_file, AudioEngine, Sound, MAX_TICK_SECS};
+use squad_roulette::format::{pool_breakdown_text, pool_summary_text};
+use squad_roulette::model::{
+    apply_filters, build_pool, count_exclusions, refresh_blocker, spin_blocker, trim_history_snapshots, unix_now,
+    AnimationSettings, AudioSettings, DeferredRebuild, FilterSettings, HistoryEntry, Language, PoolPick, PoolStats,
+    RouletteState, RowSize, ServerItem, ServerLists, ServerPool, Settings, SpinBlocker, SpinRecord, View,
+    WheelMode,
+};
+use squad_roulette::selection::{choose_outcome, crossfade_names, pick_winner, SpinOutcome};
+use squad_roulette::snapshot;
+use squad_roulette::storage::{
+    data_dir, load_lists, load_settings, prune_aside_files, save_lists, save_settings, write_atomic,
+};
+use squad_roulette::worker::{Generation, Worker};
+
+use crate::platform::{always_on_top_supported, apply_window_level, send_desktop_notification};
+use crate::repaint::RepaintScheduler;
+use crate::speech::{speakable, Speaker};
+#[cfg(feature = "tray")]
+use crate::tray::{spawn_tray, TrayCommand, TrayHandle};
+use crate::ui::theme::{apply_theme, palette_for};
+use crate::ui::widgets::{CachedDetails, ServerDetails};
+
+const SKIP_REMAINING_SECS: f32 = 0.6;
+const CLICK_BURST_MAX: u32 = 2;
+const WHIRR_MIN_ROWS: u32 = 5;
+const MAX_CLICK_SPACING: f32 = 0.03;
+const AUDIO_DEVICE_POLL_SECS: f32 = 3.0;
+const DRUMROLL_START_LEVEL: f32 = 0.25;
+const DRUMROLL_END_LEVEL: f32 = 0.6;
+// Wheel speed, in items per second, at which each faster variant takes over.
+const CLICK_PITCH_SPEEDS: [f32; 4] = [1.5, 4.0, 12.0, 30.0];
+const NUDGE_CLICK_GAIN: f32 = 2.5;
+// The horizontal strip sweeps its ticks across this many steps, left to right, before starting over.
+const PAN_SWEEP_TICKS: i64 = 5;
+// Speed, in items per second, at which the sweep reaches the full stereo width; slower ticks stay nearer the middle.
+const PAN_FULL_SPEED: f32 = 12.0;
+const VERTICAL_PAN: f32 = 0.5;
+const WINNER_REVEAL_TIME: f32 = 0.3;
+const WINNER_HIGHLIGHT_TIME: f32 = 2.0;
+const MAX_PARTICLES: usize = 150;
+pub(crate) const CONFETTI_LIFETIME: f32 = 1.5;
+const LONG_FETCH_SECS: f32 = 5.0;
+const CONFETTI_GRAVITY: f32 = 600.0;
+const CONFETTI_COLORS: [egui::Color32; 6] = [
+    egui::Color32::GOLD,
+    egui::Color32::from_rgb(255, 80, 80),
+    egui::Color32::from_rgb(80, 200, 255),
+    egui::Color32::from_rgb(120, 230, 120),
+    egui::Color32::from_rgb(255, 150, 230),
+    egui::Color32::WHITE,
+];
+const COUNTDOWN_SECS: u32 = 3;
+pub(crate) const MIN_HIT_SIZE: f32 = 32.0;
+pub(crate) const PERF_SAMPLE_FRAMES: usize = 120;
+const SERVER_CACHE_FILE: &str = "servers.bin";
+pub(crate) const UI_SCALE_MIN: f32 = 0.75;
+pub(crate) const UI_SCALE_MAX: f32 = 2.0;
+// Only this many of the latest spins keep the pool they were drawn from for replays.
+const HISTORY_SNAPSHOTS_KEPT: usize = 20;
+// Corrupt files and settings backups set aside in the data directory, newest first, up to this many bytes.
+const ASIDE_FILES_MAX_BYTES: u64 = 1024 * 1024;
+
+// What a replay sets aside: roulette_servers is whatever the wheel displays, and while a replay shows a
+// recorded pool the live one lives here, so leaving the replay puts the wheel back exactly as it was.
+pub(crate) struct LiveState {
+    servers: ServerPool,
+    selected_server: Option<PoolPick>,
+    popout_winner: Option<ServerItem>,
+    manual_pick: bool,
+    scroll: f32,
+    state: RouletteState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SortColumn {
+    Name,
+    Map,
+    Mode,
+    Players,
+    Queue,
+    Country,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum EmptyAction {
+    WidenPlayers,
+    AllCountries,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RowAction {
+    ToggleFavorite,
+    Blacklist,
+    ExcludeForSession,
+    CopyName,
+    CopyInfo,
+    OpenBattleMetrics,
+    PickAsWinner,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Particle {
+    pub(crate) pos: egui::Vec2,
+    vel: egui::Vec2,
+    pub(crate) color: egui::Color32,
+    pub(crate) size: f32,
+    pub(crate) life: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum ResetScope {
+    Filters,
+    AudioAnimation,
+    Everything,
+}
+
+pub(crate) struct Toast {
+    pub(crate) text: String,
+    pub(crate) copy: Option<String>,
+    pub(crate) created_at: Instant,
+}
+
+pub struct RouletteApp {
+    pub(crate) settings: Settings,
+    pub(crate) lists: ServerLists,
+    // Everything the last fetch saw; fetched_servers is what the filters keep of it.
+    pub(crate) raw_servers: Vec<ServerItem>,
+    // The raw list may be missing servers with this many players or fewer; None when nothing was left unfetched.
+    pub(crate) raw_floor: Option<u32>,
+    pub(crate) fetched_servers: Vec<ServerItem>,
+    pub(crate) roulette_servers: ServerPool,
+    pub(crate) pool_details: Vec<ServerDetails>,
+    // The language and dark mode pool_details were built for.
+    pub(crate) pool_details_for: (Language, bool),
+    pub(crate) winner_details: CachedDetails,
+    pub(crate) popout_details: CachedDetails,
+    // The status bar's "Refreshed …" text and the (refresh, age bucket, language) it was built for.
+    pub(crate) freshness: (Option<(Option<u64>, u64, Language)>, String),
+    pub(crate) pool_stats: PoolStats,
+    pub(crate) pool_summary: String,
+    pub(crate) pool_breakdown: String,
+    pub(crate) pool_header: String,
+    pub(crate) selected_server: Option<PoolPick>,
+    pub(crate) roulette_state: RouletteState,
+    pub(crate) roulette_rx: Option<Receiver<FetchMessage>>,
+    pub(crate) worker: Worker,
+    // Cleared on the first frame, when the startup time is logged.
+    pub(crate) launched_at: Option<Instant>,
+    pub(crate) resting: Option<RestingView>,
+    pub(crate) fetch_generation: Generation,
+    pub(crate) cache_generation: Generation,
+    pub(crate) fetch_progress: Option<FetchProgress>,
+    pub(crate) fetch_summary: Option<FetchSummary>,
+    pub(crate) last_refresh: Option<u64>,
+    pub(crate) fetch_started_at: Option<Instant>,
+    pub(crate) window_focused: bool,
+    pub(crate) show_popout: bool,
+    pub(crate) popout_winner: Option<ServerItem>,
+    pub(crate) settings_dirty: bool,
+    #[cfg(feature = "tray")]
+    pub(crate) tray: Option<TrayHandle>,
+    #[cfg(feature = "tray")]
+    pub(crate) window_hidden: bool,
+    #[cfg(feature = "tray")]
+    pub(crate) quit_requested: bool,
+    pub(crate) spin: Option<SpinAnimation>,
+    pub(crate) crossfade: Option<Crossfade>,
+    pub(crate) current_spin: Option<SpinRecord>,
+    pub(crate) replay: Option<LiveState>,
+    pub(crate) paused: bool,
+    pub(crate) current_scroll: f32,
+    pub(crate) target_scroll: f32,
+    pub(crate) current_animation_duration: f32,
+    pub(crate) audio: AudioEngine,
+    pub(crate) audio_checked_at: Option<Instant>,
+    pub(crate) speaker: Speaker,
+    // Samples of the click being tuned in the sound lab, redrawn only when a slider moves.
+    pub(crate) lab_preview: Vec<f32>,
+    pub(crate) repaint: RepaintScheduler,
+    pub(crate) countdown_started_at: Option<Instant>,
+    pub(crate) countdown_shown: u32,
+    pub(crate) browsing: bool,
+    pub(crate) manual_pick: bool,
+    pub(crate) presentation: bool,
+    pub(crate) show_perf_overlay: bool,
+    pub(crate) frame_times: VecDeque<f64>,
+    pub(crate) pre_presentation_rect: Option<egui::Rect>,
+    pub(crate) scroll_speed: f32,
+    pub(crate) needs_update: bool,
+    pub(crate) show_reset_dialog: bool,
+    pub(crate) confirm_full_reset: bool,
+    pub(crate) show_log_panel: bool,
+    pub(crate) log_level_filter: Level,
+    pub(crate) config_warning: Option<String>,
+    pub(crate) show_onboarding: bool,
+    pub(crate) theme_dirty: bool,
+    pub(crate) applied_ui_scale: f32,
+    pub(crate) ui_scale_dirty: bool,
+    pub(crate) window_level_dirty: bool,
+    pub(crate) view: View,
+    pub(crate) table_sort: SortColumn,
+    pub(crate) table_sort_desc: bool,
+    pub(crate) table_search: String,
+    pub(crate) winner_revealed_at: Option<Instant>,
+    pub(crate) winner_reveal: f32,
+    pub(crate) highlight_started_at: Option<Instant>,
+    pub(crate) highlight: f32,
+    pub(crate) particles: Vec<Particle>,
+    pub(crate) confetti_origin: egui::Pos2,
+    pub(crate) history: Vec<HistoryEntry>,
+    pub(crate) toasts: Vec<Toast>,
+    pub(crate) session_excluded: std::collections::HashSet<String>,
+    pub(crate) pool_rebuild: DeferredRebuild,
+}
+
+impl Default for RouletteApp {
+    fn default() -> Self {
+        Self {
+            settings: Settings::default(),
+            lists: ServerLists::default(),
+            raw_servers: Vec::new(),
+            raw_floor: None,
+            fetched_servers: Vec::new(),
+            roulette_servers: ServerPool::default(),
+            pool_details: Vec::new(),
+            pool_details_for: (Language::default(), true),
+            winner_details: CachedDetails::default(),
+            popout_details: CachedDetails::default(),
+            freshness: (None, String::new()),
+            pool_stats: PoolStats::default(),
+            pool_summary: String::new(),
+            pool_breakdown: String::new(),
+            pool_header: String::new(),
+            selected_server: None,
+            roulette_state: RouletteState::Ready,
+            roulette_rx: None,
+            worker: Worker::new(),
+            launched_at: None,
+            resting: None,
+            fetch_generation: Generation::default(),
+            cache_generation: Generation::default(),
+            fetch_progress: None,
+            fetch_summary: None,
+            last_refresh: None,
+            fetch_started_at: None,
+            window_focused: true,
+            show_popout: false,
+            popout_winner: None,
+            settings_dirty: false,
+            #[cfg(feature = "tray")]
+            tray: None,
+            #[cfg(feature = "tray")]
+            window_hidden: false,
+            #[cfg(feature = "tray")]
+            quit_requested: false,
+            spin: None,
+            crossfade: None,
+            current_spin: None,
+            replay: None,
+            paused: false,
+            current_scroll: 0.0,
+            target_scroll: 0.0,
+            current_animation_duration: 10.0, 
+            audio: AudioEngine::new(),
+            audio_checked_at: None,
+            speaker: Speaker::new(),
+            lab_preview: Vec::new(),
+            repaint: RepaintScheduler::default(),
+            countdown_started_at: None,
+            countdown_shown: 0,
+            browsing: false,
+            manual_pick: false,
+            presentation: false,
+            show_perf_overlay: false,
+            frame_times: VecDeque::with_capacity(PERF_SAMPLE_FRAMES + 1),
+            pre_presentation_rect: None,
+            scroll_speed: 0.0,
+            needs_update: true,
+            show_reset_dialog: false,
+            confirm_full_reset: false,
+            show_log_panel: false,
+            log_level_filter: Level::INFO,
+            config_warning: None,
+            show_onboarding: false,
+            theme_dirty: false,
+            applied_ui_scale: 1.0,
+            ui_scale_dirty: false,
+            window_level_dirty: false,
+            view: View::Roulette,
+            table_sort: SortColumn::Players,
+            table_sort_desc: true,
+            table_search: String::new(),
+            winner_revealed_at: None,
+            winner_reveal: 1.0,
+            highlight_started_at: None,
+            highlight: 0.0,
+            particles: Vec::with_capacity(MAX_PARTICLES),
+            confetti_origin: egui::Pos2::ZERO,
+            history: Vec::new(),
+            toasts: Vec::new(),
+            session_excluded: std::collections::HashSet::new(),
+            pool_rebuild: DeferredRebuild::default(),
+        }
+    }
+}
+
+impl RouletteApp {
+    pub fn new(cc: &eframe::CreationContext<'_>, launched_at: Instant) -> Self {
+        cc.egui_ctx.all_styles_mut(|style| {
+            style.spacing.item_spacing = egui::vec2(10.0, 15.0);
+            style.spacing.interact_size.y = MIN_HIT_SIZE;
+        });
+        let (settings, config_warning) = load_settings();
+        if let Some(dir) = data_dir() { prune_aside_files(&dir, ASIDE_FILES_MAX_BYTES); }
+        apply_theme(&cc.egui_ctx, settings.appearance.theme);
+        let applied_ui_scale = settings.appearance.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+        cc.egui_ctx.set_zoom_factor(applied_ui_scale);
+        let mut settings = settings;
+        if settings.appearance.always_on_top {
+            if always_on_top_supported() { apply_window_level(&cc.egui_ctx, true); } else { settings.appearance.always_on_top = false; }
+        }
+        let show_onboarding = !settings.onboarding_done;
+        let view = settings.last_view;
+        #[cfg(feature = "tray")]
+        let tray = spawn_tray(cc.egui_ctx.clone(), settings.language);
+        let mut app = Self {
+            settings,
+            lists: load_lists(),
+            config_warning,
+            show_onboarding,
+            applied_ui_scale,
+            view,
+            #[cfg(feature = "tray")]
+            tray,
+            launched_at: Some(launched_at),
+            ..Default::default()
+        };
+        app.reopen_audio();
+        app.load_custom_sounds();
+        app.restore_server_cache(&cc.egui_ctx);
+        app
+    }
+
+    // The last session's list fills the wheel until the next refresh. A cache that won't decode is
+    // deleted and fetched again rather than trusted.
+    fn restore_server_cache(&mut self, ctx: &egui::Context) {
+        let Some(path) = data_dir().map(|dir| dir.join(SERVER_CACHE_FILE)) else { return; };
+        let bytes = match std::fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound { warn!("failed to read {}: {}", path.display(), e); }
+                return;
+            }
+        };
+        match snapshot::decode(&bytes) {
+            Ok(snapshot) => {
+                info!("restored {} cached servers", snapshot.servers.len());
+                // Whether the original fetch was complete isn't recorded, so only ranges above the quietest cached server count as covered.
+                self.raw_floor = snapshot.servers.iter().map(|s| s.players).min().filter(|&floor| floor > 0);
+                self.raw_servers = snapshot.servers;
+                self.fetch_summary = Some(FetchSummary { total_seen: self.raw_servers.len(), ..FetchSummary::default() });
+                self.last_refresh = Some(snapshot.saved_at);
+                self.refilter();
+            }
+            Err(e) => {
+                warn!("server cache {} is unreadable ({}), refetching", path.display(), e);
+                if let Err(e) = std::fs::remove_file(&path) { warn!("failed to remove {}: {}", path.display(), e); }
+                self.start_fetch(ctx.clone());
+            }
+        }
+    }
+
+    // Written on the worker; a newer list supersedes one still waiting to be written.
+    fn save_server_cache(&self) {
+        let Some(path) = data_dir().map(|dir| dir.join(SERVER_CACHE_FILE)) else { return; };
+        let bytes = snapshot::encode(&self.raw_servers, unix_now());
+        self.worker.submit(self.cache_generation.advance(), move |_| {
+            if let Err(e) = write_atomic(&path, &bytes) { warn!("failed to save the server cache: {}", e); }
+        });
+    }
+
+    pub(crate) fn reopen_audio(&mut self) {
+        self.audio.open(self.settings.audio.device.as_deref());
+        self.audio.set_volume(self.settings.audio.gain());
+        self.audio_checked_at = Some(Instant::now());
+    }
+
+    // cpal doesn't report hot-plugging, so every few seconds compare what we play to with what we should:
+    // a new system default, the chosen device coming back, or a dead output all trigger a re-open.
+    fn check_audio_device(&mut self) {
+        if self.audio_checked_at.is_some_and(|at| at.elapsed().as_secs_f32() < AUDIO_DEVICE_POLL_SECS) { return; }
+        self.audio_checked_at = Some(Instant::now());
+        let current = self.audio.device_name().map(str::to_string);
+        let stale = match &self.settings.audio.device {
+            _ if self.audio.initializing() => false,
+            _ if !self.audio.available() || self.audio.lost() => true,
+            None => AudioEngine::default_device_name() != current,
+            Some(wanted) => current.as_ref() != Some(wanted) && AudioEngine::output_devices().contains(wanted),
+        };
+        if stale {
+            info!("audio output changed, reopening (was {:?})", current);
+            self.reopen_audio();
+        }
+    }
+
+    // Swaps in the configured sound files, falling back to the built-in sounds for any that won't load.
+    pub(crate) fn load_custom_sounds(&mut self) {
+        let lang = self.settings.language;
+        let tick_file = self.settings.audio.tick_file.trim().to_string();
+        let win_file = self.settings.audio.win_file.trim().to_string();
+        self.audio.set_theme(self.settings.audio.theme, &self.settings.audio.click());
+        self.audio.reset_custom();
+        if !tick_file.is_empty() {
+            match load_sound_file(&tick_file, Some(MAX_TICK_SECS)) {
+                Ok(sound) => self.audio.set_click(sound),
+                Err(e) => {
+                    warn!("failed to load tick sound {}: {}", tick_file, e);
+                    self.push_toast(format!("{} {}: {}", lang.pick("Не вдалося завантажити звук тіку, грає стандартний —", "Could not load the tick sound, using the built-in one —"), tick_file, e), None);
+                }
+            }
+        }
+        if !win_file.is_empty() {
+            match load_sound_file(&win_file, None) {
+                Ok(sound) => self.audio.set_fanfare(sound),
+                Err(e) => {
+                    warn!("failed to load win sound {}: {}", win_file, e);
+                    self.push_toast(format!("{} {}: {}", lang.pick("Не вдалося завантажити звук перемоги, грає стандартний —", "Could not load the win sound, using the built-in one —"), win_file, e), None);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn can_reset(&self) -> bool {
+        !matches!(self.roulette_state, RouletteState::Loading | RouletteState::Countdown | RouletteState::Spinning)
+    }
+
+    pub(crate) fn reset_settings(&mut self, scope: ResetScope) {
+        if !self.can_reset() { return; }
+        match scope {
+            ResetScope::Filters => {
+                let changed = self.settings.filters != FilterSettings::default();
+                self.settings.filters = FilterSettings::default();
+                if changed { self.on_filters_changed(); }
+            }
+            ResetScope::AudioAnimation => {
+                self.settings.audio = AudioSettings::default();
+                self.settings.animation = AnimationSettings::default();
+                self.reopen_audio();
+                self.load_custom_sounds();
+            }
+            ResetScope::Everything => {
+                let filters_changed = self.settings.filters != FilterSettings::default();
+                self.settings = Settings::default();
+                if filters_changed { self.on_filters_changed(); }
+                self.show_onboarding = true;
+                self.theme_dirty = true;
+                self.ui_scale_dirty = true;
+                self.window_level_dirty = true;
+                self.lists = ServerLists::default();
+                save_lists(&self.lists);
+                self.history.clear();
+                self.session_excluded.clear();
+                if let Some(path) = data_dir().map(|dir| dir.join(SERVER_CACHE_FILE)) { let _ = std::fs::remove_file(path); }
+                self.rebuild_pool();
+                self.reopen_audio();
+                self.load_custom_sounds();
+            }
+        }
+        save_settings(&self.settings);
+    }
+    
+    // The filters work on the raw list in memory; only ranges it can't answer need another fetch.
+    pub(crate) fn raw_covers_filters(&self) -> bool {
+        !self.raw_servers.is_empty() && self.raw_floor.is_none_or(|floor| self.settings.filters.min_players > floor)
+    }
+
+    pub(crate) fn refilter(&mut self) {
+        let filters = &self.settings.filters;
+        self.fetched_servers = apply_filters(&self.raw_servers, filters).into_iter().map(|i| self.raw_servers[i].clone()).collect();
+        if let Some(summary) = self.fetch_summary.as_mut() {
+            (summary.excluded_region, summary.excluded_players) = count_exclusions(&self.raw_servers, filters);
+        }
+        self.rebuild_pool();
+    }
+
+    pub(crate) fn on_filters_changed(&mut self) {
+        if self.roulette_state == RouletteState::Loading { return; }
+        if self.raw_covers_filters() { self.refilter(); } else { self.needs_update = true; }
+    }
+
+    pub(crate) fn rebuild_pool(&mut self) {
+        if !self.pool_rebuild.request(self.roulette_state) { return; }
+        let pool = build_pool(&self.fetched_servers, &self.lists, &self.session_excluded);
+        if let Some(live) = self.replay.as_mut() { live.servers = pool; return; }
+        self.roulette_servers = pool;
+        self.on_pool_changed();
+    }
+
+    pub(crate) fn on_pool_changed(&mut self) {
+        let lang = self.settings.language;
+        let colors = palette_for(self.pool_details_for.1);
+        self.pool_details = self.roulette_servers.iter().map(|s| ServerDetails::new(s, lang, colors)).collect();
+        self.pool_stats = PoolStats::new(&self.roulette_servers);
+        self.pool_summary = pool_summary_text(&self.pool_stats, lang);
+        self.pool_breakdown = pool_breakdown_text(&self.pool_stats, lang);
+        self.pool_header = format!("{} {} {}", lang.pick("Пул:", "Pool:"), self.roulette_servers.len(), lang.pick("серверів", "servers"));
+    }
+
+    pub(crate) fn row_height(&self) -> f32 {
+        self.settings.appearance.row_size.height()
+    }
+
+    pub(crate) fn item_pitch(&self) -> f32 {
+        self.settings.appearance.wheel_mode.pitch(self.row_height())
+    }
+
+    // Rescales the resting position so the same server stays under the pointer after switching layouts.
+    pub(crate) fn set_wheel_mode(&mut self, mode: WheelMode) {
+        let old_pitch = self.item_pitch();
+        self.settings.appearance.wheel_mode = mode;
+        self.rescale_scroll(old_pitch);
+    }
+
+    pub(crate) fn set_row_size(&mut self, size: RowSize) {
+        let old_pitch = self.item_pitch();
+        self.settings.appearance.row_size = size;
+        self.rescale_scroll(old_pitch);
+    }
+
+    fn rescale_scroll(&mut self, old_pitch: f32) {
+        let scale = self.item_pitch() / old_pitch;
+        self.current_scroll *= scale;
+        self.target_scroll *= scale;
+        save_settings(&self.settings);
+    }
+
+    // Same rounding as the click sound and the winner highlight, so this always names the row under the pointer.
+    pub(crate) fn passing_name(&self) -> Option<&str> {
+        if self.roulette_servers.is_empty() { return None; }
+        if let Some(crossfade) = &self.crossfade { return self.roulette_servers.get(crossfade.current()).map(|s| s.name.as_str()); }
+        let idx = server_at(self.pointer_row(), self.roulette_servers.len());
+        Some(self.roulette_servers[idx].name.as_str())
+    }
+
+    pub(crate) fn pointer_row(&self) -> i64 {
+        let (scroll, pitch) = (self.current_scroll, self.item_pitch());
+        match &self.resting {
+            Some(rest) if rest.scroll == scroll && rest.pitch == pitch => rest.row,
+            _ => row_at(scroll, pitch),
+        }
+    }
+
+    // The rows a view `view` long shows around current_scroll; while the wheel is still they come from `resting`.
+    pub(crate) fn rows_in_view(&mut self, view: f32, pitch: f32) -> std::ops::Range<i64> {
+        let scroll = self.current_scroll;
+        if self.roulette_state == RouletteState::Spinning { return visible_rows(scroll, view, pitch); }
+        match &self.resting {
+            Some(rest) if rest.matches(scroll, view, pitch) => rest.rows.clone(),
+            _ => self.resting.insert(RestingView::new(scroll, view, pitch)).rows.clone(),
+        }
+    }
+
+    pub(crate) fn switch_view(&mut self, view: View) {
+        self.view = view;
+        self.settings.last_view = view;
+        self.settings_dirty = true;
+    }
+
+    fn pick_as_winner(&mut self, idx: usize) {
+        if self.roulette_state == RouletteState::Spinning { return; }
+        let Some(pick) = PoolPick::new(&self.roulette_servers, idx) else { return; };
+        self.selected_server = Some(pick);
+        self.manual_pick = true;
+        self.snap_scroll_to(idx);
+        self.finish_spin();
+        self.switch_view(View::Roulette);
+    }
+
+    fn snap_scroll_to(&mut self, idx: usize) {
+        self.current_scroll = idx as f32 * self.item_pitch();
+        self.target_scroll = self.current_scroll;
+        self.browsing = false;
+    }
+
+    fn finish_spin(&mut self) {
+        self.roulette_state = RouletteState::Finished;
+        self.spin = None;
+        self.crossfade = None;
+        self.paused = false;
+        self.audio.stop_loop();
+        let replayed = self.replay.is_some();
+        if let Some(winner) = &self.selected_server {
+            let spin = self.current_spin.take();
+            if !replayed {
+                self.history.push(HistoryEntry::new(ServerItem::clone(winner), self.manual_pick, spin));
+                trim_history_snapshots(&mut self.history, HISTORY_SNAPSHOTS_KEPT);
+            }
+            if !replayed { self.popout_winner = Some(ServerItem::clone(winner)); }
+            let now = Instant::now();
+            self.winner_revealed_at = Some(now);
+            self.winner_reveal = 0.0;
+            self.highlight_started_at = Some(now);
+            self.highlight = 1.0;
+            let lang = self.settings.language;
+            if !replayed { self.notify_if_unfocused(format!("🎰 {} {} — {} {}", lang.pick("Переможець:", "Winner:"), winner.name, winner.map, winner.players_text())); }
+            if !replayed && self.settings.audio.announce_winner && self.settings.audio.gain() > 0.0 {
+                let phrase = format!("{} {}, {}", lang.pick("Переможець:", "Winner:"), speakable(&winner.name), speakable(&winner.map));
+                self.speaker.speak(phrase, lang.code());
+            }
+            if !self.effects_reduced() { self.burst_confetti(); }
+        }
+        if self.pool_rebuild.settle() { self.rebuild_pool(); }
+    }
+
+    pub(crate) fn exit_replay(&mut self) {
+        let Some(live) = self.replay.take() else { return; };
+        self.spin = None;
+        self.crossfade = None;
+        self.paused = false;
+        self.current_spin = None;
+        self.roulette_servers = live.servers;
+        self.on_pool_changed();
+        self.selected_server = live.selected_server;
+        self.popout_winner = live.popout_winner;
+        self.manual_pick = live.manual_pick;
+        self.current_scroll = live.scroll;
+        self.target_scroll = live.scroll;
+        self.roulette_state = live.state;
+        self.highlight = 0.0;
+        self.highlight_started_at = None;
+        if self.pool_rebuild.settle() { self.rebuild_pool(); }
+    }
+
+    // What the long-lived collections hold; pools shared between the wheel and spin records count once.
+    pub(crate) fn memory_stats(&self, lang: Language) -> String {
+        let snapshots: Vec<&ServerPool> = self.history.iter().filter_map(|e| e.spin.as_ref().map(|s| &s.pool)).collect();
+        let mut pools = std::collections::HashSet::new();
+        let held: usize = snapshots.iter().copied()
+            .chain(std::iter::once(&self.roulette_servers))
+            .chain(self.replay.as_ref().map(|live| &live.servers))
+            .filter(|pool| pools.insert(pool.as_ptr()))
+            .map(|pool| pool.len())
+            .sum();
+        format!(
+            "{} {} ({} {}, {} {})\n{} {} ({} {})\n{} {} · {} {}",
+            lang.pick("історія:", "history:"), self.history.len(),
+            snapshots.len(), lang.pick("зі знімками", "with snapshots"), HISTORY_SNAPSHOTS_KEPT, lang.pick("макс.", "max"),
+            lang.pick("пули:", "pools:"), pools.len(), held, lang.pick("серверів", "servers"),
+            lang.pick("отримано:", "fetched:"), self.fetched_servers.len(),
+            lang.pick("рядків у кеші:", "cached rows:"), self.pool_details.len(),
+        )
+    }
+
+    fn notify_if_unfocused(&self, body: String) {
+        if self.settings.notifications.enabled && !self.window_focused { send_desktop_notification(body); }
+    }
+
+    pub(crate) fn reopen_history_entry(&mut self, index: usize) {
+        if matches!(self.roulette_state, RouletteState::Loading | RouletteState::Countdown | RouletteState::Spinning) { return; }
+        self.exit_replay();
+        let Some(entry) = self.history.get(index) else { return; };
+        self.selected_server = Some(PoolPick::single(entry.server.clone()));
+        self.popout_winner = Some(entry.server.clone());
+        self.manual_pick = entry.manual;
+        self.roulette_state = RouletteState::Finished;
+        self.winner_revealed_at = Some(Instant::now());
+        self.winner_reveal = 0.0;
+    }
+
+    pub(crate) fn open_in_browser(&mut self, url: String) {
+        if let Err(e) = webbrowser::open(&url) {
+            warn!("failed to open {}: {}", url, e);
+            let text = format!("{}\n{}", self.settings.language.pick("Не вдалося відкрити браузер:", "Could not open the browser:"), url);
+            self.push_toast(text, Some(url));
+        }
+    }
+
+    fn push_toast(&mut self, text: String, copy: Option<String>) {
+        self.toasts.push(Toast { text, copy, created_at: Instant::now() });
+    }
+
+    fn burst_confetti(&mut self) {
+        let mut rng = rand::thread_rng();
+        self.particles.clear();
+        for _ in 0..MAX_PARTICLES {
+            let angle: f32 = rng.gen_range(-std::f32::consts::PI..0.0);
+            let speed: f32 = rng.gen_range(150.0..550.0);
+            self.particles.push(Particle {
+                pos: egui::vec2(rng.gen_range(-120.0..120.0), 0.0),
+                vel: egui::vec2(angle.cos() * speed, angle.sin() * speed),
+                color: CONFETTI_COLORS[rng.gen_range(0..CONFETTI_COLORS.len())],
+                size: rng.gen_range(3.0..7.0),
+                life: rng.gen_range(CONFETTI_LIFETIME * 0.6..CONFETTI_LIFETIME),
+            });
+        }
+    }
+
+    fn update_particles(&mut self, ctx: &egui::Context) {
+        if self.particles.is_empty() { return; }
+        let dt = ctx.input(|i| i.stable_dt).min(0.05);
+        for p in &mut self.particles {
+            p.vel.y += CONFETTI_GRAVITY * dt;
+            p.pos += p.vel * dt;
+            p.life -= dt;
+        }
+        self.particles.retain(|p| p.life > 0.0);
+        if !self.particles.is_empty() { self.repaint.request_now(); }
+    }
+
+    pub(crate) fn start_fetch(&mut self, ctx: egui::Context) {
+        if self.refresh_blocker().is_some() { return; }
+        self.exit_replay();
+        self.fetched_servers.clear();
+        self.raw_servers.clear();
+        self.roulette_servers = ServerPool::default();
+        self.on_pool_changed();
+        self.selected_server = None;
+        self.roulette_state = RouletteState::Loading;
+        self.needs_update = false;
+
+        let (tx, rx) = channel();
+        self.roulette_rx = Some(rx);
+        self.fetch_progress = None;
+        self.fetch_started_at = Some(Instant::now());
+        self.current_scroll = 0.0;
+        self.browsing = false;
+        let ticket = self.fetch_generation.advance();
+        self.worker.submit(ticket, move |ticket| {
+            fetch_roulette_servers(tx, ticket);
+            ctx.request_repaint();
+        });
+    }
+
+    fn can_spin(&self) -> bool {
+        self.spin_blocker().is_none()
+    }
+
+    pub(crate) fn refresh_blocker(&self) -> Option<SpinBlocker> {
+        refresh_blocker(self.roulette_state)
+    }
+
+    pub(crate) fn spin_blocker(&self) -> Option<SpinBlocker> {
+        let live_pool_size = self.replay.as_ref().map(|live| live.servers.len());
+        spin_blocker(self.roulette_state, self.paused, self.needs_update, self.roulette_servers.len(), live_pool_size)
+    }
+
+    pub(crate) fn skip_spin(&mut self) {
+        if self.roulette_state != RouletteState::Spinning { return; }
+        self.paused = false;
+        if let Some(spin) = self.spin.as_mut() { spin.fast_forward(SKIP_REMAINING_SECS); }
+        self.audio.stop_all();
+        if let Some(crossfade) = self.crossfade.as_mut() { crossfade.finish(); }
+    }
+
+    // Time only moves in advance_spin, so skipping it freezes the wheel and its clicks exactly where they are.
+    pub(crate) fn toggle_pause(&mut self) {
+        if self.roulette_state != RouletteState::Spinning { return; }
+        self.paused = !self.paused;
+        self.audio.pause_loop(self.paused);
+        // A frozen wheel is legible, so drop the motion blur until it moves again.
+        self.scroll_speed = 0.0;
+    }
+
+    // Abandons the spin: the pre-drawn winner is dropped unannounced and nothing reaches the history.
+    pub(crate) fn cancel_spin(&mut self) {
+        if self.roulette_state != RouletteState::Spinning { return; }
+        self.spin = None;
+        self.crossfade = None;
+        self.paused = false;
+        self.selected_server = None;
+        self.current_spin = None;
+        self.target_scroll = self.current_scroll;
+        self.roulette_state = RouletteState::Ready;
+        self.audio.stop_all();
+        self.exit_replay();
+        info!("spin cancelled");
+    }
+
+    // Reduced motion implies reduced effects: no confetti, shimmer or zooming rows either.
+    pub(crate) fn effects_reduced(&self) -> bool {
+        self.settings.animation.reduced_effects || self.settings.animation.reduced_motion
+    }
+
+    #[cfg(feature = "tray")]
+    fn handle_tray(&mut self, ctx: &egui::Context) {
+        let Some(tray) = &self.tray else { return; };
+        let commands: Vec<TrayCommand> = tray.rx.try_iter().collect();
+        if ctx.input(|i| i.viewport().close_requested()) && self.settings.appearance.minimize_to_tray && !self.quit_requested {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+            self.window_hidden = true;
+        }
+        for command in commands {
+            match command {
+                TrayCommand::ToggleWindow => {
+                    self.window_hidden = !self.window_hidden;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(!self.window_hidden));
+                    if !self.window_hidden { ctx.send_viewport_cmd(egui::ViewportCommand::Focus); }
+                }
+                TrayCommand::Refresh => self.start_fetch(ctx.clone()),
+                TrayCommand::Spin => {
+                    self.window_hidden = false;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+                    self.request_spin();
+                }
+                TrayCommand::Quit => {
+                    self.quit_requested = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+            }
+        }
+    }
+
+    fn toggle_presentation(&mut self, ctx: &egui::Context) {
+        self.presentation = !self.presentation;
+        if self.presentation {
+            self.pre_presentation_rect = ctx.input(|i| {
+                let viewport = i.viewport();
+                viewport.outer_rect.zip(viewport.inner_rect).map(|(outer, inner)| egui::Rect::from_min_size(outer.min, inner.size()))
+            });
+            self.switch_view(View::Roulette);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(true));
+        } else {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Fullscreen(false));
+            if let Some(rect) = self.pre_presentation_rect.take() {
+                ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(rect.size()));
+                ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(rect.min));
+            }
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| i.key_pressed(egui::Key::F11)) { self.toggle_presentation(ctx); }
+        if ctx.memory(|m| m.focused().is_some()) { return; }
+        let (space, refresh, pause, escape, copy) = ctx.input(|i| (
+            i.key_pressed(egui::Key::Space),
+            i.key_pressed(egui::Key::R) && i.modifiers.is_none(),
+            i.key_pressed(egui::Key::P) && i.modifiers.is_none(),
+            i.key_pressed(egui::Key::Escape),
+            i.events.iter().any(|e| matches!(e, egui::Event::Copy)),
+        ));
+        if space { self.request_spin(); }
+        if refresh { self.start_fetch(ctx.clone()); }
+        if pause { self.toggle_pause(); }
+        if escape {
+            self.cancel_countdown();
+            self.skip_spin();
+        }
+        if copy && self.roulette_state == RouletteState::Finished {
+            if let Some(winner) = &self.selected_server {
+                let text = winner.info_text(self.settings.language);
+                ctx.output_mut(|o| o.copied_text = text);
+            }
+        }
+    }
+
+    fn draw_outcome(&self) -> SpinOutcome {
+        let seed = rand::random::<u64>();
+        let outcome = choose_outcome(seed, &self.pool_stats, self.current_scroll / self.item_pitch(), &self.settings.animation);
+        info!("spin seed {:016x}: winner {} of {}", seed, outcome.winner_idx, self.roulette_servers.len());
+        outcome
+    }
+
+    pub(crate) fn quick_pick(&mut self) {
+        self.exit_replay();
+        if self.roulette_servers.is_empty() { return; }
+        let seed = rand::random::<u64>();
+        let winner_idx = pick_winner(seed, &self.pool_stats);
+        info!("quick pick seed {:016x}: winner {} of {}", seed, winner_idx, self.roulette_servers.len());
+        self.selected_server = PoolPick::new(&self.roulette_servers, winner_idx);
+        self.manual_pick = false;
+        self.snap_scroll_to(winner_idx);
+        if self.settings.audio.instant_fanfare { self.play_fanfare(); }
+        self.finish_spin();
+    }
+
+    fn start_spin(&mut self) {
+        self.exit_replay();
+        if self.roulette_servers.is_empty() { return; }
+        let outcome = self.draw_outcome();
+        self.current_spin = Some(SpinRecord { outcome, pool: self.roulette_servers.clone() });
+        self.animate_outcome(outcome);
+    }
+
+    pub(crate) fn replay_spin(&mut self, index: usize) {
+        if !matches!(self.roulette_state, RouletteState::Ready | RouletteState::Finished) { return; }
+        let Some(record) = self.history.get(index).and_then(|e| e.spin.clone()) else { return; };
+        info!("replaying spin seed {:016x}", record.outcome.seed);
+        if self.replay.is_none() {
+            self.replay = Some(LiveState {
+                servers: std::mem::take(&mut self.roulette_servers),
+                selected_server: self.selected_server.clone(),
+                popout_winner: self.popout_winner.clone(),
+                manual_pick: self.manual_pick,
+                scroll: self.current_scroll,
+                state: self.roulette_state,
+            });
+        }
+        self.roulette_servers = record.pool;
+        self.on_pool_changed();
+        self.switch_view(View::Roulette);
+        self.animate_outcome(record.outcome);
+    }
+
+    fn animate_outcome(&mut self, outcome: SpinOutcome) {
+        let winner_idx = outcome.winner_idx;
+        self.selected_server = PoolPick::new(&self.roulette_servers, winner_idx);
+        self.manual_pick = false;
+        let single = self.roulette_servers.len() == 1;
+        if self.settings.animation.instant || single {
+            self.snap_scroll_to(winner_idx);
+            self.play_click();
+            if self.settings.audio.instant_fanfare { self.play_fanfare(); }
+            self.finish_spin();
+            if single { self.push_toast(self.settings.language.pick("У пулі лише один сервер — крутити нічого.", "Only one server in the pool, so there was nothing to spin.").to_string(), None); }
+            return;
+        }
+        if self.settings.animation.reduced_motion {
+            // The wheel rests on the winner behind the fade, so it is in place once the names stop.
+            self.snap_scroll_to(winner_idx);
+            self.crossfade = Some(Crossfade::new(crossfade_names(&outcome, self.roulette_servers.len())));
+            self.roulette_state = RouletteState::Spinning;
+            self.start_drumroll();
+            return;
+        }
+        self.current_animation_duration = outcome.duration;
+        let spin = plan_spin(&outcome, self.roulette_servers.len(), self.item_pitch());
+        self.current_scroll = spin.start();
+        self.target_scroll = spin.target();
+        self.browsing = false;
+        self.spin = Some(spin);
+        self.roulette_state = RouletteState::Spinning;
+        self.start_drumroll();
+    }
+
+    fn advance_spin(&mut self, ctx: &egui::Context) {
+        if self.paused { return; }
+        let dt = ctx.input(|i| i.stable_dt).min(0.1);
+        if let Some(crossfade) = self.crossfade.as_mut() {
+            let changed = crossfade.advance(dt);
+            let done = crossfade.done();
+            if changed { self.play_click(); }
+            if done { self.play_fanfare(); self.finish_spin(); } else { self.repaint.request_now(); }
+            return;
+        }
+        let Some(spin) = self.spin.as_mut() else { return; };
+        let was_returning = spin.phase() == SpinPhase::NearMissBack;
+        let nudging = spin.phase() == SpinPhase::Nudge;
+        let scroll = spin.advance(dt);
+        let phase = spin.phase();
+        let from = self.current_scroll;
+        let crossed = rows_crossed(from, scroll, self.item_pitch());
+        self.scroll_speed = (scroll - from).abs() / dt.max(0.001);
+        self.current_scroll = scroll;
+        if phase == SpinPhase::NearMissBack && !was_returning { self.play_click(); }
+        if nudging && crossed > 0 { self.audio.play_tick(self.audio.click(), Duration::ZERO, NUDGE_CLICK_GAIN); } else { self.play_crossings(from, crossed, dt); }
+        // Played here rather than in finish_spin, which also runs for quick picks and manual picks.
+        if phase == SpinPhase::Done { self.play_fanfare(); self.finish_spin(); } else { self.repaint.request_now(); }
+    }
+
+    pub(crate) fn request_spin(&mut self) {
+        if !self.can_spin() { return; }
+        if self.settings.animation.countdown && !self.settings.animation.instant {
+            self.roulette_state = RouletteState::Countdown;
+            self.countdown_started_at = Some(Instant::now());
+            self.countdown_shown = 0;
+        } else {
+            self.start_spin();
+        }
+    }
+
+    fn update_countdown(&mut self) {
+        if self.roulette_state != RouletteState::Countdown { self.countdown_started_at = None; return; }
+        let Some(started_at) = self.countdown_started_at else { return; };
+        let elapsed = started_at.elapsed().as_secs_f32();
+        if elapsed >= COUNTDOWN_SECS as f32 {
+            self.countdown_started_at = None;
+            if self.settings.audio.countdown_sounds { self.play_sound(self.audio.launch_beep()); }
+            self.start_spin();
+            if self.roulette_state == RouletteState::Countdown { self.roulette_state = RouletteState::Ready; }
+            return;
+        }
+        let remaining = COUNTDOWN_SECS - elapsed as u32;
+        if remaining != self.countdown_shown {
+            self.countdown_shown = remaining;
+            self.play_beep();
+        }
+        self.repaint.request_now();
+    }
+
+    fn cancel_countdown(&mut self) {
+        if self.roulette_state != RouletteState::Countdown { return; }
+        self.countdown_started_at = None;
+        self.roulette_state = RouletteState::Ready;
+    }
+
+    pub(crate) fn play_click(&self) {
+        self.play_sound(self.current_click(0.0));
+    }
+
+    // Ticks rise in pitch while the wheel races and thud as it slows; outside a spin the plain click plays.
+    fn current_click(&self, pan: f32) -> &Sound {
+        let Some(spin) = &self.spin else { return self.audio.click(); };
+        let bucket = CLICK_PITCH_SPEEDS.iter().filter(|&&threshold| self.tick_speed(spin) > threshold).count();
+        self.audio.tick(bucket, pan)
+    }
+
+    // Each step of the slow finale darts across a row, so its frame speed says nothing about the suspense.
+    fn tick_speed(&self, spin: &SpinAnimation) -> f32 {
+        if spin.phase() == SpinPhase::Ticks { 0.0 } else { self.scroll_speed / self.item_pitch() }
+    }
+
+    // Where the tick for the row the pointer just reached sits in the stereo field. The strip sweeps
+    // with the cards, wider the faster they go and backwards when they run back; the other modes only
+    // alternate a little either side.
+    fn tick_pan(&self, row: i64) -> f32 {
+        match self.settings.appearance.wheel_mode {
+            WheelMode::Horizontal => {
+                let sweep = row.rem_euclid(PAN_SWEEP_TICKS) as f32 / (PAN_SWEEP_TICKS - 1) as f32 * 2.0 - 1.0;
+                let speed = self.spin.as_ref().map_or(0.0, |spin| self.tick_speed(spin));
+                sweep * (speed / PAN_FULL_SPEED).clamp(0.3, 1.0)
+            }
+            _ => if row % 2 == 0 { VERTICAL_PAN } else { -VERTICAL_PAN },
+        }
+    }
+
+    // Spread a frame's clicks over the frame so the rhythm follows the wheel; past a handful per frame
+    // they would only pile up, so one whirr stands in for them.
+    fn play_crossings(&self, from: f32, crossed: u32, dt: f32) {
+        if crossed >= WHIRR_MIN_ROWS { self.play_sound(self.audio.whirr()); return; }
+        let clicks = crossed.min(CLICK_BURST_MAX);
+        let spacing = (dt / clicks.max(1) as f32).min(MAX_CLICK_SPACING);
+        let pitch = self.item_pitch();
+        let last_row = ((self.current_scroll + pitch * 0.5) / pitch).floor() as i64;
+        let step = if self.current_scroll >= from { 1 } else { -1 };
+        for i in 0..clicks {
+            let row = last_row - step * (clicks - 1 - i) as i64;
+            self.play_delayed(self.current_click(self.tick_pan(row)), Duration::from_secs_f32(spacing * i as f32));
+        }
+    }
+
+    fn start_drumroll(&mut self) {
+        if !self.settings.audio.drumroll { return; }
+        let drumroll = self.audio.drumroll().clone();
+        self.audio.start_loop(&drumroll, DRUMROLL_START_LEVEL);
+    }
+
+    // Swells a little as the wheel nears the end; finish_spin and skip_spin cut it off.
+    fn update_drumroll(&mut self) {
+        let progress = match (&self.spin, &self.crossfade) {
+            (Some(spin), _) => spin.progress(),
+            (None, Some(crossfade)) => crossfade.progress(),
+            (None, None) => return,
+        };
+        self.audio.set_loop_level(DRUMROLL_START_LEVEL + (DRUMROLL_END_LEVEL - DRUMROLL_START_LEVEL) * progress.clamp(0.0, 1.0));
+    }
+
+    fn play_beep(&self) {
+        if self.settings.audio.countdown_sounds { self.play_sound(self.audio.beep()); }
+    }
+
+    pub(crate) fn play_fanfare(&mut self) {
+        let fanfare = self.audio.fanfare().clone();
+        self.audio.play_music(&fanfare);
+    }
+
+    fn play_sound(&self, sound: &Sound) {
+        self.play_delayed(sound, Duration::ZERO);
+    }
+
+    fn play_delayed(&self, sound: &Sound, delay: Duration) {
+        self.audio.play_tick(sound, delay, 1.0);
+    }
+
+    pub(crate) fn apply_row_actions(&mut self, ctx: &egui::Context, actions: Vec<(usize, RowAction)>) {
+        // Resolve indices up front: blacklisting or excluding rebuilds the pool and shifts them.
+        let resolved: Vec<(ServerItem, RowAction)> = actions.into_iter()
+            .filter_map(|(idx, action)| self.roulette_servers.get(idx).map(|server| (server.clone(), action)))
+            .collect();
+        let lang = self.settings.language;
+        for (server, action) in resolved {
+            match action {
+                RowAction::ToggleFavorite => {
+                    self.lists.toggle_favorite(&server);
+                    save_lists(&self.lists);
+                }
+                RowAction::Blacklist => {
+                    self.lists.add_to_blacklist(&server);
+                    save_lists(&self.lists);
+                    self.rebuild_pool();
+                }
+                RowAction::ExcludeForSession => {
+                    self.session_excluded.insert(server.id.clone());
+                    self.rebuild_pool();
+                }
+                RowAction::CopyName => ctx.output_mut(|o| o.copied_text = server.name.clone()),
+                RowAction::CopyInfo => ctx.output_mut(|o| o.copied_text = server.info_text(lang)),
+                RowAction::OpenBattleMetrics => self.open_in_browser(server.battlemetrics_url()),
+                RowAction::PickAsWinner => {
+                    if let Some(idx) = self.roulette_servers.iter().position(|s| s.id == server.id) { self.pick_as_winner(idx); }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn apply_ui_scale(&mut self, ctx: &egui::Context) {
+        let scale = self.settings.appearance.ui_scale.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+        ctx.set_zoom_factor(scale);
+        self.applied_ui_scale = scale;
+    }
+}
+
+impl eframe::App for RouletteApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.repaint.begin_frame(ctx);
+        if let Some(launched_at) = self.launched_at.take() { info!("first frame {:.0?} after launch", launched_at.elapsed()); }
+        // The output opens in the background; a spin that started without it picks the drumroll up once it's there.
+        let had_audio = self.audio.available();
+        self.audio.poll();
+        if self.audio.initializing() { self.repaint.request_after(Duration::from_millis(50)); }
+        if !had_audio && self.audio.available() && self.roulette_state == RouletteState::Spinning { self.start_drumroll(); }
+        self.window_focused = ctx.input(|i| i.viewport().focused).unwrap_or(true);
+        self.audio.set_volume(self.settings.audio.gain());
+        // A lost output is worth reconnecting even mid-spin; otherwise the device only changes between spins.
+        if self.roulette_state == RouletteState::Spinning { self.update_drumroll(); }
+        if self.roulette_state != RouletteState::Spinning || self.audio.lost() { self.check_audio_device(); }
+        if let Some(rx) = &self.roulette_rx {
+            let mut done = None;
+            while let Ok(message) = rx.try_recv() {
+                match message {
+                    FetchMessage::Progress(progress) => self.fetch_progress = Some(progress),
+                    FetchMessage::Page(servers) => {
+                        let filters = &self.settings.filters;
+                        self.fetched_servers.extend(apply_filters(&servers, filters).into_iter().map(|i| servers[i].clone()));
+                        self.raw_servers.extend(servers);
+                    }
+                    FetchMessage::Done(servers, summary) => done = Some((servers, summary)),
+                }
+            }
+            if let Some((servers, summary)) = done {
+                if let Some(winner) = &mut self.popout_winner {
+                    if let Some(fresh) = servers.iter().find(|s| s.id == winner.id) { *winner = fresh.clone(); }
+                }
+                self.raw_floor = (summary.truncated || summary.partial).then(|| servers.iter().map(|s| s.players).min().unwrap_or(u32::MAX));
+                self.raw_servers = servers;
+                self.fetch_summary = Some(summary);
+                if !summary.failed { self.last_refresh = Some(unix_now()); }
+                if !summary.failed && !summary.partial { self.save_server_cache(); }
+                self.refilter();
+                if self.roulette_state == RouletteState::Loading {
+                     self.roulette_state = if self.roulette_servers.is_empty() { RouletteState::Finished } else { RouletteState::Ready };
+                }
+                self.roulette_rx = None;
+                self.fetch_progress = None;
+                let long_fetch = self.fetch_started_at.take().is_some_and(|t| t.elapsed().as_secs_f32() > LONG_FETCH_SECS);
+                if long_fetch {
+                    let lang = self.settings.language;
+                    let body = if summary.failed {
+                        lang.pick("Не вдалося оновити список серверів", "Failed to refresh the server list").to_string()
+                    } else {
+                        format!("{} {}", lang.pick("Список оновлено, серверів у пулі:", "Server list refreshed, servers in pool:"), self.roulette_servers.len())
+                    };
+                    self.notify_if_unfocused(body);
+                }
+            } else {
+                self.repaint.request_after(Duration::from_millis(100));
+            }
+        }
+        
+        if self.roulette_state == RouletteState::Spinning { self.advance_spin(ctx); }
+
+        if let Some(revealed_at) = self.winner_revealed_at {
+            self.winner_reveal = (revealed_at.elapsed().as_secs_f32() / WINNER_REVEAL_TIME).min(1.0);
+            if self.winner_reveal < 1.0 { self.repaint.request_now(); } else { self.winner_revealed_at = None; }
+        }
+        if let Some(started_at) = self.highlight_started_at {
+            self.highlight = 1.0 - (started_at.elapsed().as_secs_f32() / WINNER_HIGHLIGHT_TIME).min(1.0);
+            if self.highlight > 0.0 { self.repaint.request_now(); } else { self.highlight_started_at = None; }
+        }
+        self.update_countdown();
+        self.update_particles(ctx);
+        #[cfg(feature = "tray")]
+        self.handle_tray(ctx);
+        self.handle_shortcuts(ctx);
+        self.roulette_ui(ctx);
+        self.paint_particles(ctx);
+        self.paint_countdown(ctx);
+        self.repaint.finish_frame(ctx);
+    }
+
+    fn save(&mut self, _storage: &mut dyn eframe::Storage) {
+        if self.settings_dirty {
+            save_settings(&self.settings);
+            self.settings_dirty = false;
+        }
+    }
+}
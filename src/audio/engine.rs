@@ -0,0 +1,480 @@
+use rodio::buffer::SamplesBuffer;
+use rodio::source::Buffered;
+use rodio::cpal::traits::HostTrait;
+use rodio::{DeviceTrait, OutputStream, OutputStreamHandle, Sink, Source};
+use rand::Rng;
+use std::cell::{Cell, OnceCell};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use super::{synthesize_click, ClickParams, FailureCounter, SoundTheme, MECHANICAL_CLICK, SAMPLE_RATE};
+
+// Every sound is decoded or synthesized once; clones of a Buffered share its frames, so handing one
+// to a sink per tick copies no samples.
+pub type Sound = Buffered<SamplesBuffer<f32>>;
+
+const TICK_SINKS: usize = 4;
+const TICK_FADE_SECS: f32 = 0.03;
+// Playback rates of the pitched clicks, slowest first; the middle one is the click as recorded.
+const CLICK_PITCHES: [f32; 5] = [0.7, 0.85, 1.0, 1.25, 1.5];
+// Stereo positions each pitched click is rendered at, hard left to hard right; a tick snaps to the nearest.
+const PAN_POSITIONS: [f32; 5] = [-1.0, -0.5, 0.0, 0.5, 1.0];
+const DRUMROLL_HITS_PER_SEC: u32 = 24;
+// A whole number of hits, so the loop point falls between two of them and the roll never stutters.
+const DRUMROLL_HITS: u32 = 12;
+
+pub(crate) struct FanfareParams {
+    pub(crate) notes: &'static [f32],
+    pub(crate) note_ms: u32,
+    // Level of the octave above each note; a little of it makes a bell out of a beep.
+    pub(crate) overtone: f32,
+}
+
+pub(crate) const MECHANICAL_FANFARE: FanfareParams = FanfareParams { notes: &[523.25, 659.25, 783.99, 1046.5], note_ms: 90, overtone: 0.0 };
+pub(crate) const CASINO_FANFARE: FanfareParams = FanfareParams { notes: &[1046.5, 1318.51, 1567.98, 2093.0], note_ms: 70, overtone: 0.3 };
+pub(crate) const SOFT_FANFARE: FanfareParams = FanfareParams { notes: &[392.0, 493.88, 587.33], note_ms: 140, overtone: 0.0 };
+
+impl SoundTheme {
+    fn fanfare(self) -> &'static FanfareParams {
+        match self {
+            SoundTheme::Mechanical => &MECHANICAL_FANFARE,
+            SoundTheme::Casino => &CASINO_FANFARE,
+            SoundTheme::Soft => &SOFT_FANFARE,
+        }
+    }
+}
+
+pub(crate) fn synthesize_beep(freq: f32, duration_ms: u32) -> Vec<f32> {
+    let len = SAMPLE_RATE * duration_ms / 1000;
+    (0..len)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let decay = 1.0 - i as f32 / len as f32;
+            (t * freq * std::f32::consts::TAU).sin() * decay * 0.4
+        })
+        .collect()
+}
+
+pub(crate) fn synthesize_fanfare(params: &FanfareParams) -> Vec<f32> {
+    let note_len = SAMPLE_RATE * params.note_ms / 1000;
+    params.notes.iter()
+        .flat_map(|&freq| (0..note_len).map(move |i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let decay = 1.0 - i as f32 / note_len as f32;
+            let phase = t * freq * std::f32::consts::TAU;
+            (phase.sin() + (2.0 * phase).sin() * params.overtone) / (1.0 + params.overtone) * decay.sqrt() * 0.3
+        }))
+        .collect()
+}
+
+fn synthesize_whirr() -> Sound {
+    let mut rng = rand::thread_rng();
+    // A quick rattle standing in for a run of clicks too dense to play one by one.
+    let len = SAMPLE_RATE * 150 / 1000;
+    let mut last_sample = 0.0;
+    let samples: Vec<f32> = (0..len)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let raw_noise: f32 = rng.gen_range(-1.0..1.0);
+            last_sample = last_sample * 0.7 + raw_noise * 0.3;
+            let rattle = 0.5 + 0.5 * (t * 60.0 * std::f32::consts::TAU).sin();
+            let decay = 1.0 - i as f32 / len as f32;
+            last_sample * rattle * decay * 1.5
+        })
+        .collect();
+    SamplesBuffer::new(1, SAMPLE_RATE, samples).buffered()
+}
+
+// Soft snare strokes over a low rumble, looped for as long as the wheel turns.
+fn synthesize_drumroll() -> Sound {
+    let mut rng = rand::thread_rng();
+    let hit_len = SAMPLE_RATE / DRUMROLL_HITS_PER_SEC;
+    let mut last_sample = 0.0;
+    let samples: Vec<f32> = (0..hit_len * DRUMROLL_HITS)
+        .map(|i| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let since_hit = (i % hit_len) as f32 / SAMPLE_RATE as f32;
+            let raw_noise: f32 = rng.gen_range(-1.0..1.0);
+            last_sample = last_sample * 0.5 + raw_noise * 0.5;
+            let rumble = (t * 55.0 * std::f32::consts::TAU).sin() * 0.15;
+            last_sample * (-since_hit * 60.0).exp() * 0.5 + rumble
+        })
+        .collect();
+    SamplesBuffer::new(1, SAMPLE_RATE, samples).buffered()
+}
+
+fn mono(samples: Vec<f32>) -> Sound {
+    SamplesBuffer::new(1, SAMPLE_RATE, samples).buffered()
+}
+
+// An opened output, as handed back by its thread.
+struct Opened {
+    handle: OutputStreamHandle,
+    device_name: Option<String>,
+}
+
+// cpal streams can't leave the thread that opened them, so each output gets a thread of its own that
+// opens the device, sends back the handle and then just holds the stream until `close` is dropped.
+// Opening also means the first frame never waits on the sound system.
+fn spawn_output(device: Option<String>) -> (Sender<()>, Receiver<Option<Opened>>) {
+    let (close, closed) = channel::<()>();
+    let (opened_tx, opened) = channel();
+    let spawned = thread::Builder::new().name("audio-output".into()).spawn(move || {
+        let host = rodio::cpal::default_host();
+        let named = device.as_deref().and_then(|name| host.output_devices().ok()?.find(|d| d.name().is_ok_and(|n| n == name)));
+        if device.is_some() && named.is_none() { warn!("audio device {:?} not found, using the default", device); }
+        let Some(device) = named.or_else(|| host.default_output_device()) else {
+            warn!("no audio output device");
+            let _ = opened_tx.send(None);
+            return;
+        };
+        match OutputStream::try_from_device(&device) {
+            Ok((_stream, handle)) => {
+                let _ = opened_tx.send(Some(Opened { handle, device_name: device.name().ok() }));
+                // Blocks until the engine drops its end, then lets the stream go with the thread.
+                let _ = closed.recv();
+            }
+            Err(e) => {
+                warn!("audio output unavailable: {}", e);
+                let _ = opened_tx.send(None);
+            }
+        }
+    });
+    if let Err(e) = spawned { warn!("failed to start the audio output thread: {}", e); }
+    (close, opened)
+}
+
+// Owns the output device and every sound the app makes. Short sounds go through a small pool of
+// sinks, so at most TICK_SINKS of them ever overlap; longer ones (the fanfare) get a sink of their own.
+// Sounds are synthesized the first time they're asked for, and anything played while the output is
+// still opening is dropped like a tick that finds every sink busy.
+pub struct AudioEngine {
+    close_output: Option<Sender<()>>,
+    opening: Option<Receiver<Option<Opened>>>,
+    handle: Option<OutputStreamHandle>,
+    device_name: Option<String>,
+    tick_sinks: Vec<Sink>,
+    music_sink: Option<Sink>,
+    loop_sink: Option<Sink>,
+    loop_level: f32,
+    volume: f32,
+    failures: Cell<FailureCounter>,
+    theme: SoundTheme,
+    click_params: ClickParams,
+    stock_click: OnceCell<Sound>,
+    stock_fanfare: OnceCell<Sound>,
+    custom_click: Option<Sound>,
+    custom_fanfare: Option<Sound>,
+    // Indexed by pitch, then by pan position.
+    ticks: OnceCell<Vec<Vec<Sound>>>,
+    whirr: OnceCell<Sound>,
+    beep: OnceCell<Sound>,
+    launch_beep: OnceCell<Sound>,
+    drumroll: OnceCell<Sound>,
+}
+
+impl AudioEngine {
+    // Nothing is opened or synthesized yet; `open` starts the output and sounds wait for their first use.
+    pub fn new() -> Self {
+        Self {
+            close_output: None,
+            opening: None,
+            handle: None,
+            device_name: None,
+            tick_sinks: Vec::new(),
+            music_sink: None,
+            loop_sink: None,
+            loop_level: 0.0,
+            volume: 1.0,
+            failures: Cell::new(FailureCounter::default()),
+            theme: SoundTheme::Mechanical,
+            click_params: MECHANICAL_CLICK,
+            stock_click: OnceCell::new(),
+            stock_fanfare: OnceCell::new(),
+            custom_click: None,
+            custom_fanfare: None,
+            ticks: OnceCell::new(),
+            whirr: OnceCell::new(),
+            beep: OnceCell::new(),
+            launch_beep: OnceCell::new(),
+            drumroll: OnceCell::new(),
+        }
+    }
+
+    // Starts opening the named output, or the system default when it's None or no longer plugged in.
+    // Returns at once; `poll` picks the output up when it's ready.
+    pub fn open(&mut self, device: Option<&str>) {
+        self.tick_sinks.clear();
+        self.music_sink = None;
+        self.loop_sink = None;
+        self.handle = None;
+        self.device_name = None;
+        self.failures.set(FailureCounter::default());
+        let (close, opening) = spawn_output(device.map(str::to_string));
+        self.close_output = Some(close);
+        self.opening = Some(opening);
+    }
+
+    // Called every frame; cheap unless an output has just finished opening.
+    pub fn poll(&mut self) {
+        let Some(opening) = &self.opening else { return; };
+        let opened = match opening.try_recv() {
+            Ok(opened) => opened,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => None,
+        };
+        self.opening = None;
+        match opened {
+            Some(opened) => {
+                debug!("audio output ready: {:?}", opened.device_name);
+                self.handle = Some(opened.handle);
+                self.device_name = opened.device_name;
+                self.open_sinks();
+            }
+            None => self.close_output = None,
+        }
+    }
+
+    pub fn initializing(&self) -> bool {
+        self.opening.is_some()
+    }
+
+    pub fn available(&self) -> bool {
+        !self.tick_sinks.is_empty()
+    }
+
+    // The output opened but has stopped playing, most likely because the device went away.
+    pub fn lost(&self) -> bool {
+        self.failures.get().lost(Instant::now())
+    }
+
+    pub fn device_name(&self) -> Option<&str> {
+        self.device_name.as_deref()
+    }
+
+    pub fn output_devices() -> Vec<String> {
+        rodio::cpal::default_host().output_devices().map(|devices| devices.filter_map(|d| d.name().ok()).collect()).unwrap_or_default()
+    }
+
+    pub fn default_device_name() -> Option<String> {
+        rodio::cpal::default_host().default_output_device().and_then(|d| d.name().ok())
+    }
+
+    fn new_sink(&self) -> Option<Sink> {
+        let handle = self.handle.as_ref()?;
+        match Sink::try_new(handle) {
+            Ok(sink) => {
+                sink.set_volume(self.volume);
+                Some(sink)
+            }
+            Err(e) => {
+                warn!("failed to open an audio sink: {}", e);
+                None
+            }
+        }
+    }
+
+    // Dropping a sink silences whatever it was playing, so fresh ones are both the reset and the reconnect.
+    fn open_sinks(&mut self) {
+        self.tick_sinks = (0..TICK_SINKS).filter_map(|_| self.new_sink()).collect();
+        self.music_sink = self.new_sink();
+        self.loop_sink = None;
+    }
+
+    pub fn stop_all(&mut self) {
+        self.open_sinks();
+    }
+
+    pub fn set_volume(&mut self, volume: f32) {
+        if volume == self.volume { return; }
+        self.volume = volume;
+        self.tick_sinks.iter().chain(&self.music_sink).for_each(|sink| sink.set_volume(volume));
+        if let Some(sink) = &self.loop_sink { sink.set_volume(volume * self.loop_level); }
+    }
+
+    // Starts `sound` repeating on a sink of its own at `level` of the master volume; replaces any loop already running.
+    pub fn start_loop(&mut self, sound: &Sound, level: f32) {
+        self.loop_level = level;
+        self.loop_sink = self.new_sink();
+        if let Some(sink) = &self.loop_sink {
+            sink.set_volume(self.volume * level);
+            sink.append(sound.clone().repeat_infinite());
+        }
+    }
+
+    pub fn set_loop_level(&mut self, level: f32) {
+        if level == self.loop_level { return; }
+        self.loop_level = level;
+        if let Some(sink) = &self.loop_sink { sink.set_volume(self.volume * level); }
+    }
+
+    pub fn pause_loop(&self, paused: bool) {
+        if let Some(sink) = &self.loop_sink { if paused { sink.pause() } else { sink.play() } }
+    }
+
+    pub fn stop_loop(&mut self) {
+        self.loop_sink = None;
+    }
+
+    // Takes an idle sink from the pool; with all of them busy the sound is dropped rather than queued behind them.
+    pub fn play_tick(&self, sound: &Sound, delay: Duration, gain: f32) {
+        if self.volume <= 0.0 { return; }
+        let mut failures = self.failures.get();
+        match self.tick_sinks.iter().find(|sink| sink.empty()) {
+            Some(sink) => {
+                failures.succeeded();
+                sink.append(sound.clone().amplify(gain).delay(delay));
+            }
+            None if self.tick_sinks.is_empty() => {}
+            None => {
+                if failures.failed(Instant::now()) { warn!("audio output stopped playing, will reconnect"); } else { debug!("all tick sinks busy, dropping a sound"); }
+            }
+        }
+        self.failures.set(failures);
+    }
+
+    pub fn click(&self) -> &Sound {
+        self.custom_click.as_ref().unwrap_or_else(|| self.stock_click.get_or_init(|| mono(synthesize_click(&self.click_params))))
+    }
+
+    pub fn fanfare(&self) -> &Sound {
+        self.custom_fanfare.as_ref().unwrap_or_else(|| self.stock_fanfare.get_or_init(|| mono(synthesize_fanfare(self.theme.fanfare()))))
+    }
+
+    pub fn whirr(&self) -> &Sound {
+        self.whirr.get_or_init(synthesize_whirr)
+    }
+
+    pub fn beep(&self) -> &Sound {
+        self.beep.get_or_init(|| mono(synthesize_beep(880.0, 50)))
+    }
+
+    pub fn launch_beep(&self) -> &Sound {
+        self.launch_beep.get_or_init(|| mono(synthesize_beep(1760.0, 120)))
+    }
+
+    pub fn drumroll(&self) -> &Sound {
+        self.drumroll.get_or_init(synthesize_drumroll)
+    }
+
+    // The click at a pitch bucket (0 is the deepest), placed at `pan` from -1 (left) to 1 (right).
+    pub fn tick(&self, pitch: usize, pan: f32) -> &Sound {
+        let ticks = self.ticks.get_or_init(|| panned_ticks(self.click()));
+        let Some(variants) = ticks.get(pitch.min(ticks.len().saturating_sub(1))) else { return self.click(); };
+        let nearest = PAN_POSITIONS
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| (*a - pan).abs().total_cmp(&(*b - pan).abs()))
+            .map_or(0, |(i, _)| i);
+        variants.get(nearest).unwrap_or_else(|| self.click())
+    }
+
+    pub fn play_music(&mut self, sound: &Sound) {
+        if self.volume <= 0.0 { return; }
+        if self.music_sink.as_ref().is_some_and(|sink| !sink.empty()) { self.music_sink = self.new_sink(); }
+        if let Some(sink) = &self.music_sink { sink.append(sound.clone()); }
+    }
+
+    // Drops the built-in sounds so they're regenerated for the theme; custom files, if any, still win.
+    // `click` is usually the theme's own, but the sound lab can hand in tuned parameters instead.
+    pub fn set_theme(&mut self, theme: SoundTheme, click: &ClickParams) {
+        self.theme = theme;
+        self.click_params = *click;
+        self.stock_click = OnceCell::new();
+        self.stock_fanfare = OnceCell::new();
+        self.ticks = OnceCell::new();
+    }
+
+    pub fn reset_custom(&mut self) {
+        self.custom_click = None;
+        self.custom_fanfare = None;
+        self.ticks = OnceCell::new();
+    }
+
+    pub fn set_click(&mut self, click: Sound) {
+        self.custom_click = Some(click);
+        self.ticks = OnceCell::new();
+    }
+
+    pub fn set_fanfare(&mut self, fanfare: Sound) {
+        self.custom_fanfare = Some(fanfare);
+    }
+}
+
+impl Default for AudioEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Decodes a wav/ogg/mp3 (whatever rodio's decoder understands) into a buffer that can be replayed cheaply.
+// With `max_secs` the sound is cut short with a fade-out, so a long file can't make ticks pile up.
+pub fn load_sound_file(path: &str, max_secs: Option<f32>) -> Result<Sound, String> {
+    let file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let decoder = rodio::Decoder::new(std::io::BufReader::new(file)).map_err(|e| e.to_string())?;
+    let channels = decoder.channels();
+    let sample_rate = decoder.sample_rate();
+    let mut samples: Vec<f32> = decoder.convert_samples().collect();
+    if samples.is_empty() { return Err("the file has no audio".to_string()); }
+    if let Some(max_secs) = max_secs {
+        let frame = channels as usize;
+        let max_len = (max_secs * sample_rate as f32) as usize * frame;
+        if samples.len() > max_len {
+            samples.truncate(max_len);
+            let fade_len = (TICK_FADE_SECS * sample_rate as f32) as usize * frame;
+            let fade_start = max_len - fade_len;
+            for (i, sample) in samples[fade_start..].iter_mut().enumerate() {
+                *sample *= 1.0 - (i / frame) as f32 / (fade_len / frame) as f32;
+            }
+        }
+    }
+    Ok(SamplesBuffer::new(channels, sample_rate, samples).buffered())
+}
+
+// Linear resampling: a rate above 1 makes the click shorter and higher, below 1 longer and deeper.
+fn resample(sound: &Sound, rate: f32) -> Sound {
+    let channels = sound.channels();
+    let frame = channels as usize;
+    let samples: Vec<f32> = sound.clone().collect();
+    let frames = samples.len() / frame;
+    let out_frames = (frames as f32 / rate) as usize;
+    let mut out = Vec::with_capacity(out_frames * frame);
+    for i in 0..out_frames {
+        let pos = i as f32 * rate;
+        let a = (pos as usize).min(frames.saturating_sub(1));
+        let b = (a + 1).min(frames.saturating_sub(1));
+        let t = pos.fract();
+        for c in 0..frame { out.push(samples[a * frame + c] * (1.0 - t) + samples[b * frame + c] * t); }
+    }
+    SamplesBuffer::new(channels, sound.sample_rate(), out).buffered()
+}
+
+// Folds the sound to mono and writes it into both channels with a balance law: the near side keeps
+// full level and the far side fades out, so a centred tick sounds just like the mono click.
+fn pan(sound: &Sound, pan: f32) -> Sound {
+    let frame = sound.channels() as usize;
+    let samples: Vec<f32> = sound.clone().collect();
+    let left = (1.0 - pan).min(1.0);
+    let right = (1.0 + pan).min(1.0);
+    let out: Vec<f32> = samples
+        .chunks(frame)
+        .flat_map(|chunk| {
+            let mono = chunk.iter().sum::<f32>() / chunk.len() as f32;
+            [mono * left, mono * right]
+        })
+        .collect();
+    SamplesBuffer::new(2, sound.sample_rate(), out).buffered()
+}
+
+// Every pitch × pan combination is rendered up front, so playing a tick never touches its samples.
+fn panned_ticks(click: &Sound) -> Vec<Vec<Sound>> {
+    CLICK_PITCHES
+        .iter()
+        .map(|&rate| {
+            let pitched = if rate == 1.0 { click.clone() } else { resample(click, rate) };
+            PAN_POSITIONS.iter().map(|&position| pan(&pitched, position)).collect()
+        })
+        .collect()
+}
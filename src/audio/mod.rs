@@ -0,0 +1,115 @@
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// Built with `--no-default-features` the app is silent: the stub keeps the engine's interface, so callers
+// never need to know which one they got.
+#[cfg(feature = "audio")]
+mod engine;
+#[cfg(not(feature = "audio"))]
+#[path = "stub.rs"]
+mod engine;
+
+pub use engine::{load_sound_file, AudioEngine, Sound};
+
+pub const MAX_TICK_SECS: f32 = 0.15;
+pub const SAMPLE_RATE: u32 = 44100;
+// A dead output never drains its sinks, so every tick finds them all busy. A fast spin can fill the pool
+// for a few frames on its own, however high the frame rate, but never for a whole second.
+pub const LOST_AFTER_FAILURES: u32 = 32;
+const LOST_AFTER: Duration = Duration::from_secs(1);
+
+// One-pole low-passed noise with a power-law decay, optionally mixed with a sine for a metallic ring.
+// The sound lab edits these and they're saved with the settings.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ClickParams {
+    pub duration_ms: u32,
+    // Weight of the previous sample in the low-pass filter: higher is duller.
+    pub smoothing: f32,
+    pub decay_power: f32,
+    // Peak level of the finished click, at most 1.0.
+    pub gain: f32,
+    pub tone_hz: f32,
+    pub tone_mix: f32,
+}
+
+pub const MECHANICAL_CLICK: ClickParams = ClickParams { duration_ms: 20, smoothing: 0.85, decay_power: 2.0, gain: 0.9, tone_hz: 0.0, tone_mix: 0.0 };
+pub const CASINO_CLICK: ClickParams = ClickParams { duration_ms: 35, smoothing: 0.6, decay_power: 3.0, gain: 0.8, tone_hz: 2093.0, tone_mix: 0.5 };
+pub const SOFT_CLICK: ClickParams = ClickParams { duration_ms: 30, smoothing: 0.95, decay_power: 1.5, gain: 0.7, tone_hz: 0.0, tone_mix: 0.0 };
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum SoundTheme {
+    #[default]
+    Mechanical,
+    Casino,
+    Soft,
+}
+
+impl SoundTheme {
+    pub const ALL: [SoundTheme; 3] = [SoundTheme::Mechanical, SoundTheme::Casino, SoundTheme::Soft];
+
+    pub fn click(self) -> &'static ClickParams {
+        match self {
+            SoundTheme::Mechanical => &MECHANICAL_CLICK,
+            SoundTheme::Casino => &CASINO_CLICK,
+            SoundTheme::Soft => &SOFT_CLICK,
+        }
+    }
+}
+
+// The noise is normalized to a peak of 1.0 before the tone is mixed in and the gain applied, so however
+// the filter is set the click never goes past `gain`, and `gain` never past full scale.
+pub fn synthesize_click(params: &ClickParams) -> Vec<f32> {
+    let num_samples = SAMPLE_RATE * params.duration_ms / 1000;
+    let mut rng = rand::thread_rng();
+    let mut last_sample = 0.0;
+    let noise: Vec<f32> = (0..num_samples)
+        .map(|_| {
+            let raw_noise: f32 = rng.gen_range(-1.0..1.0);
+            last_sample = last_sample * params.smoothing + raw_noise * (1.0 - params.smoothing);
+            last_sample
+        })
+        .collect();
+    let peak = noise.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs())).max(f32::EPSILON);
+    let gain = params.gain.clamp(0.0, 1.0);
+    let tone_mix = params.tone_mix.clamp(0.0, 1.0);
+    noise
+        .iter()
+        .enumerate()
+        .map(|(i, sample)| {
+            let t = i as f32 / SAMPLE_RATE as f32;
+            let tone = (t * params.tone_hz * std::f32::consts::TAU).sin();
+            let decay = (1.0 - i as f32 / num_samples as f32).powf(params.decay_power);
+            (sample / peak * (1.0 - tone_mix) + tone * tone_mix) * decay * gain
+        })
+        .collect()
+}
+
+// Sounds dropped in a row for want of an idle sink; any sound that gets through clears it. The output
+// counts as lost only after LOST_AFTER_FAILURES drops spread over at least LOST_AFTER.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FailureCounter {
+    failures: u32,
+    first_failure: Option<Instant>,
+    reported: bool,
+}
+
+impl FailureCounter {
+    pub fn succeeded(&mut self) {
+        *self = Self::default();
+    }
+
+    // Returns true for the drop that makes the output count as lost, so the caller can say so once.
+    pub fn failed(&mut self, now: Instant) -> bool {
+        self.failures = self.failures.saturating_add(1);
+        self.first_failure.get_or_insert(now);
+        let report = self.lost(now) && !self.reported;
+        self.reported |= report;
+        report
+    }
+
+    pub fn lost(&self, now: Instant) -> bool {
+        self.failures >= LOST_AFTER_FAILURES && self.first_failure.is_some_and(|first| now.duration_since(first) >= LOST_AFTER)
+    }
+}
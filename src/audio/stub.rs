@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use super::{ClickParams, SoundTheme};
+
+// Stands in for a decoded sound; there's nothing to hold when nothing can be played.
+#[derive(Clone, Debug, Default)]
+pub struct Sound;
+
+// The engine's interface with every call a no-op, for builds without the `audio` feature.
+#[derive(Default)]
+pub struct AudioEngine {
+    silence: Sound,
+}
+
+impl AudioEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, _device: Option<&str>) {}
+
+    pub fn poll(&mut self) {}
+
+    pub fn initializing(&self) -> bool {
+        false
+    }
+
+    pub fn available(&self) -> bool {
+        false
+    }
+
+    pub fn lost(&self) -> bool {
+        false
+    }
+
+    pub fn device_name(&self) -> Option<&str> {
+        None
+    }
+
+    pub fn output_devices() -> Vec<String> {
+        Vec::new()
+    }
+
+    pub fn default_device_name() -> Option<String> {
+        None
+    }
+
+    pub fn stop_all(&mut self) {}
+
+    pub fn set_volume(&mut self, _volume: f32) {}
+
+    pub fn start_loop(&mut self, _sound: &Sound, _level: f32) {}
+
+    pub fn set_loop_level(&mut self, _level: f32) {}
+
+    pub fn pause_loop(&self, _paused: bool) {}
+
+    pub fn stop_loop(&mut self) {}
+
+    pub fn play_tick(&self, _sound: &Sound, _delay: Duration, _gain: f32) {}
+
+    pub fn click(&self) -> &Sound {
+        &self.silence
+    }
+
+    pub fn fanfare(&self) -> &Sound {
+        &self.silence
+    }
+
+    pub fn whirr(&self) -> &Sound {
+        &self.silence
+    }
+
+    pub fn beep(&self) -> &Sound {
+        &self.silence
+    }
+
+    pub fn launch_beep(&self) -> &Sound {
+        &self.silence
+    }
+
+    pub fn drumroll(&self) -> &Sound {
+        &self.silence
+    }
+
+    pub fn tick(&self, _pitch: usize, _pan: f32) -> &Sound {
+        &self.silence
+    }
+
+    pub fn play_music(&mut self, _sound: &Sound) {}
+
+    pub fn set_theme(&mut self, _theme: SoundTheme, _click: &ClickParams) {}
+
+    pub fn reset_custom(&mut self) {}
+
+    pub fn set_click(&mut self, _click: Sound) {}
+
+    pub fn set_fanfare(&mut self, _fanfare: Sound) {}
+}
+
+pub fn load_sound_file(_path: &str, _max_secs: Option<f32>) -> Result<Sound, String> {
+    Err("this build has no audio support".to_string())
+}
@@ -0,0 +1,84 @@
+// Text the app shows or copies, in the chosen language. Nothing here draws, so it's tested without a window.
+
+use crate::model::{country_flag, unix_now, Language, PoolStats, ServerItem, BREAKDOWN_TOP};
+
+/// Groups digits in threes: a narrow no-break space in Ukrainian, a comma in English.
+pub fn format_thousands(value: u64, lang: Language) -> String {
+    let separator = lang.pick("\u{202F}", ",");
+    let digits = value.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3 * separator.len());
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) { out.push_str(separator); }
+        out.push(c);
+    }
+    out
+}
+
+/// How long ago a unix timestamp was, to the minute under an hour and to the hour after.
+pub fn format_ago(timestamp: u64, lang: Language) -> String {
+    let secs = ago_bucket(timestamp);
+    if secs < 60 { lang.pick("щойно", "just now").to_string() }
+    else if secs < 3600 { format!("{} {}", secs / 60, lang.pick("хв тому", "min ago")) }
+    else { format!("{} {}", secs / 3600, lang.pick("год тому", "h ago")) }
+}
+
+/// Seconds since a unix timestamp, rounded down to the unit format_ago shows, so its text changes only when this does.
+pub fn ago_bucket(timestamp: u64) -> u64 {
+    let secs = unix_now().saturating_sub(timestamp);
+    if secs < 60 { 0 } else if secs < 3600 { secs / 60 * 60 } else { secs / 3600 * 3600 }
+}
+
+/// One line about the pool: servers, players online and the average fill. Empty for an empty pool.
+pub fn pool_summary_text(stats: &PoolStats, lang: Language) -> String {
+    let count = stats.servers as u64;
+    if count == 0 { return String::new(); }
+    format!("{} {} · {} {} · {} {}/{}",
+        format_thousands(count, lang), lang.pick("серверів", "servers"),
+        format_thousands(stats.players, lang), lang.pick("гравців онлайн", "players online"),
+        lang.pick("у середньому", "avg"), stats.players / count, stats.capacity / count)
+}
+
+/// The pool's most common countries and maps, BREAKDOWN_TOP of each.
+pub fn pool_breakdown_text(stats: &PoolStats, lang: Language) -> String {
+    let top = |counts: &[(String, usize)], label: fn(&str) -> String| {
+        let mut text = counts.iter().take(BREAKDOWN_TOP).map(|(value, n)| format!("{} {}", label(value), n)).collect::<Vec<_>>().join(" · ");
+        if counts.len() > BREAKDOWN_TOP { text.push_str(" · …"); }
+        text
+    };
+    format!("{} {}\n{} {}",
+        lang.pick("Країни:", "Countries:"), top(&stats.countries, |code| format!("{} {}", country_flag(code), code)),
+        lang.pick("Мапи:", "Maps:"), top(&stats.maps, str::to_string))
+}
+
+/// Backslash-escapes the characters Discord reads as markdown, so a server name shows as written.
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '~' | '`' | '|' | '>' | '[' | ']') { escaped.push('\\'); }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Fills the `{name}`, `{map}`, `{mode}`, `{players}` and `{link}` placeholders of a Discord message
+/// template. Names are markdown-escaped; unknown placeholders are kept as written.
+pub fn render_discord_message(template: &str, server: &ServerItem) -> String {
+    let mut out = String::with_capacity(template.len() + server.name.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let tail = &rest[start..];
+        let Some(end) = tail.find('}') else { break; };
+        out.push_str(&rest[..start]);
+        match &tail[1..end] {
+            "name" => out.push_str(&escape_markdown(&server.name)),
+            "map" => out.push_str(&escape_markdown(&server.map)),
+            "mode" => out.push_str(&escape_markdown(&server.mode)),
+            "players" => out.push_str(&server.players_text()),
+            "link" => out.push_str(&server.battlemetrics_url()),
+            _ => out.push_str(&tail[..=end]),
+        }
+        rest = &tail[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
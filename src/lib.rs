@@ -0,0 +1,14 @@
+//! The parts of the roulette that don't need a window: talking to BattleMetrics, the settings and
+//! server model, filtering, picking a winner, and the spin animation with the wheel maths it runs on.
+//! The app in main.rs is built on top, and the integration tests and benches use it directly. Nothing
+//! here depends on egui or eframe; the binary tells `storage` where the data directory is.
+
+pub mod animation;
+pub mod api;
+pub mod audio;
+pub mod format;
+pub mod model;
+pub mod selection;
+pub mod snapshot;
+pub mod storage;
+pub mod worker;
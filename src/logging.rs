@@ -0,0 +1,73 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use tracing::{warn, Level};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use squad_roulette::storage::data_dir;
+
+const LOG_FILE_PREFIX: &str = "squad_roulette";
+const LOG_FILES_KEPT: usize = 7;
+const LOG_VIEW_LINES: usize = 200;
+
+lazy_static! {
+    pub(crate) static ref LOG_LINES: Mutex<VecDeque<LogLine>> = Mutex::new(VecDeque::with_capacity(LOG_VIEW_LINES));
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct LogLine {
+    pub(crate) level: Level,
+    pub(crate) text: String,
+}
+
+struct LogBufferLayer;
+
+struct MessageVisitor(String);
+
+impl tracing::field::Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        use std::fmt::Write;
+        if field.name() == "message" { let _ = write!(self.0, "{:?}", value); }
+        else { let _ = write!(self.0, " {}={:?}", field.name(), value); }
+    }
+}
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: tracing_subscriber::layer::Context<'_, S>) {
+        let mut visitor = MessageVisitor(String::new());
+        event.record(&mut visitor);
+        let level = *event.metadata().level();
+        let secs = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0) % 86400;
+        let text = format!("{:02}:{:02}:{:02} {:>5} {}", secs / 3600, secs / 60 % 60, secs % 60, level, visitor.0);
+        if let Ok(mut lines) = LOG_LINES.lock() {
+            if lines.len() >= LOG_VIEW_LINES { lines.pop_front(); }
+            lines.push_back(LogLine { level, text });
+        }
+    }
+}
+
+pub fn init_logging() -> Option<tracing_appender::non_blocking::WorkerGuard> {
+    let level = if cfg!(debug_assertions) { Level::DEBUG } else { Level::INFO };
+    let filter = tracing_subscriber::filter::LevelFilter::from_level(level);
+    let appender = data_dir().and_then(|dir| {
+        tracing_appender::rolling::Builder::new()
+            .rotation(tracing_appender::rolling::Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix("log")
+            .max_log_files(LOG_FILES_KEPT)
+            .build(dir.join("logs"))
+            .ok()
+    });
+    let (file_layer, guard) = match appender {
+        Some(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(tracing_subscriber::fmt::layer().with_writer(writer).with_ansi(false)), Some(guard))
+        }
+        None => (None, None),
+    };
+    tracing_subscriber::registry().with(filter).with(file_layer).with(LogBufferLayer).init();
+    if guard.is_none() { warn!("log directory unavailable, file logging disabled"); }
+    guard
+}
@@ -0,0 +1,723 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use lazy_static::lazy_static;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::animation::Easing;
+use crate::audio::{ClickParams, SoundTheme};
+use crate::selection::SpinOutcome;
+
+pub const SPIN_DURATION_MIN: f32 = 2.0;
+pub const SPIN_DURATION_MAX: f32 = 30.0;
+pub const ROW_HEIGHT: f32 = 80.0;           
+pub const CARD_WIDTH: f32 = 200.0;
+const POPULATION_SWEET_MIN: u32 = 85;
+const POPULATION_SWEET_MAX: u32 = 97;
+const POPULATION_BUSY_MIN: u32 = 60;
+const DEFAULT_DISCORD_TEMPLATE: &str = "🎰 Сервер на вечір: **{name}** — {map} — {players} — <{link}>";
+const BATTLEMETRICS_SERVER_URL: &str = "https://www.battlemetrics.com/servers/squad";
+pub(crate) const CONFIG_VERSION: u32 = 3;
+
+const EU_COUNTRIES_LIST: &str = "DE,FR,PL,GB,UA,NL,CZ,SK,IT,ES,AT,BE,DK,SE,NO,FI,IE,TR"; 
+
+const NA_COUNTRIES_LIST: &str = "US,CA,MX";
+
+const COUNTRY_NAMES: &[(&str, &str, &str)] = &[
+    ("AT", "Австрія", "Austria"), ("BE", "Бельгія", "Belgium"), ("CA", "Канада", "Canada"),
+    ("CZ", "Чехія", "Czechia"), ("DE", "Німеччина", "Germany"), ("DK", "Данія", "Denmark"),
+    ("ES", "Іспанія", "Spain"), ("FI", "Фінляндія", "Finland"), ("FR", "Франція", "France"),
+    ("GB", "Велика Британія", "United Kingdom"), ("IE", "Ірландія", "Ireland"), ("IT", "Італія", "Italy"),
+    ("MX", "Мексика", "Mexico"), ("NL", "Нідерланди", "Netherlands"), ("NO", "Норвегія", "Norway"),
+    ("PL", "Польща", "Poland"), ("SE", "Швеція", "Sweden"), ("SK", "Словаччина", "Slovakia"),
+    ("TR", "Туреччина", "Turkey"), ("UA", "Україна", "Ukraine"), ("US", "США", "United States"),
+];
+
+pub fn country_flag(code: &str) -> String {
+    if code.len() != 2 || !code.chars().all(|c| c.is_ascii_alphabetic()) { return "🏳".to_string(); }
+    code.to_ascii_uppercase().chars().filter_map(|c| char::from_u32(0x1F1E6 + (c as u32 - 'A' as u32))).collect()
+}
+
+pub fn country_name(code: &str, lang: Language) -> Option<&'static str> {
+    COUNTRY_NAMES.iter().find(|(c, _, _)| *c == code).map(|(_, uk, en)| lang.pick(uk, en))
+}
+
+lazy_static! {
+    static ref EU_SET: std::collections::HashSet<String> = {
+        EU_COUNTRIES_LIST.split(',').map(|s| s.to_string()).collect()
+    };
+    static ref NA_SET: std::collections::HashSet<String> = {
+        NA_COUNTRIES_LIST.split(',').map(|s| s.to_string()).collect()
+    };
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ServerItem {
+    pub id: String,
+    pub name: String,
+    pub players: u32,
+    pub max_players: u32,
+    pub map: String,
+    pub mode: String,
+    pub country: String,
+    pub queue: u32,
+    pub ip: String,
+    pub port: u32,
+    pub rank: Option<u32>,
+    pub licensed: bool,
+}
+
+impl ServerItem {
+    pub fn battlemetrics_url(&self) -> String {
+        format!("{}/{}", BATTLEMETRICS_SERVER_URL, self.id)
+    }
+
+    pub fn join_url(&self) -> Option<String> {
+        if self.ip.is_empty() { return None; }
+        Some(format!("steam://connect/{}:{}", self.ip, self.port))
+    }
+
+    pub fn players_text(&self) -> String {
+        if self.queue > 0 { format!("{}/{} (+{})", self.players, self.max_players, self.queue) }
+        else { format!("{}/{}", self.players, self.max_players) }
+    }
+
+    pub fn info_text(&self, lang: Language) -> String {
+        let country = country_name(&self.country, lang).unwrap_or(&self.country);
+        format!("{} — {} ({}) — {} — {} {}", self.name, self.map, self.mode, self.players_text(), country_flag(&self.country), country)
+    }
+}
+
+// Built once per fetch or filter change and shared from there: the wheel, a spin's record, a replay's
+// stash and the winner all hold the same list rather than copies of it.
+pub type ServerPool = Arc<[ServerItem]>;
+
+// The winner as a place in the pool it was drawn from; it dereferences to the server itself.
+#[derive(Clone, Debug)]
+pub struct PoolPick {
+    pool: ServerPool,
+    index: usize,
+}
+
+impl PoolPick {
+    pub fn new(pool: &ServerPool, index: usize) -> Option<Self> {
+        (index < pool.len()).then(|| Self { pool: pool.clone(), index })
+    }
+
+    // A server that isn't in the current pool, such as one reopened from the history.
+    pub fn single(server: ServerItem) -> Self {
+        Self { pool: Arc::from([server]), index: 0 }
+    }
+}
+
+impl std::ops::Deref for PoolPick {
+    type Target = ServerItem;
+
+    fn deref(&self) -> &ServerItem {
+        &self.pool[self.index]
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: u64,
+    pub server: ServerItem,
+    pub manual: bool,
+    pub spin: Option<SpinRecord>,
+}
+
+impl HistoryEntry {
+    pub fn new(server: ServerItem, manual: bool, spin: Option<SpinRecord>) -> Self {
+        Self { timestamp: unix_now(), server, manual, spin }
+    }
+}
+
+// Each auto-refresh builds a new pool, and every spin record holds on to the one it was drawn from, so
+// over a long evening the history would keep all of them alive. Past the latest `keep` spins an entry
+// remembers just its winner and can no longer be replayed.
+pub fn trim_history_snapshots(history: &mut [HistoryEntry], keep: usize) {
+    let mut kept = 0;
+    for entry in history.iter_mut().rev().filter(|e| e.spin.is_some()) {
+        if kept < keep { kept += 1; } else { entry.spin = None; }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpinRecord {
+    pub outcome: SpinOutcome,
+    pub pool: ServerPool,
+}
+
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+struct ListedServer {
+    id: String,
+    name: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ServerLists {
+    favorites: Vec<ListedServer>,
+    blacklist: Vec<ListedServer>,
+}
+
+impl ServerLists {
+    pub fn is_favorite(&self, id: &str) -> bool {
+        self.favorites.iter().any(|s| s.id == id)
+    }
+
+    pub fn is_blacklisted(&self, id: &str) -> bool {
+        self.blacklist.iter().any(|s| s.id == id)
+    }
+
+    pub fn toggle_favorite(&mut self, server: &ServerItem) {
+        if self.is_favorite(&server.id) { self.favorites.retain(|s| s.id != server.id); }
+        else { self.favorites.push(ListedServer { id: server.id.clone(), name: server.name.clone() }); }
+    }
+
+    pub fn add_to_blacklist(&mut self, server: &ServerItem) {
+        if !self.is_blacklisted(&server.id) { self.blacklist.push(ListedServer { id: server.id.clone(), name: server.name.clone() }); }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum View {
+    #[default]
+    Roulette,
+    Table,
+    History,
+    Settings,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RouletteState {
+    Ready,
+    Loading,
+    Countdown,
+    Spinning,
+    Finished,
+}
+
+// Why the spin or refresh button is greyed out, shown as its hover text.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpinBlocker {
+    Loading,
+    Spinning,
+    Paused,
+    Stale,
+    EmptyPool,
+}
+
+impl SpinBlocker {
+    pub fn reason(self, lang: Language) -> &'static str {
+        match self {
+            SpinBlocker::Loading => lang.pick("Йде завантаження — зачекайте кілька секунд", "Loading servers — wait a few seconds"),
+            SpinBlocker::Spinning => lang.pick("Рулетка вже крутиться — Esc, щоб пропустити", "Already spinning — press Esc to skip"),
+            SpinBlocker::Paused => lang.pick("Рулетку призупинено — P, щоб продовжити", "The spin is paused — press P to resume"),
+            SpinBlocker::Stale => lang.pick("Дані застаріли — натисніть Оновити", "Data is stale — press Refresh"),
+            SpinBlocker::EmptyPool => lang.pick("Пул порожній — розширте фільтри й оновіть", "The pool is empty — widen the filters and refresh"),
+        }
+    }
+}
+
+/// What stops a refresh: one would swap the pool out from under the wheel, so it waits for the same
+/// states a spin does.
+pub fn refresh_blocker(state: RouletteState) -> Option<SpinBlocker> {
+    match state {
+        RouletteState::Loading => Some(SpinBlocker::Loading),
+        RouletteState::Countdown | RouletteState::Spinning => Some(SpinBlocker::Spinning),
+        _ => None,
+    }
+}
+
+/// What stops a new spin, if anything. `live_pool_size` is Some while a past spin is replayed: a new
+/// spin leaves the replay, so it is the live pool, not the replayed `pool_size`, that must have servers.
+pub fn spin_blocker(state: RouletteState, paused: bool, needs_update: bool, pool_size: usize, live_pool_size: Option<usize>) -> Option<SpinBlocker> {
+    if state == RouletteState::Spinning && paused { return Some(SpinBlocker::Paused); }
+    if let Some(blocker) = refresh_blocker(state) { return Some(blocker); }
+    if needs_update { return Some(SpinBlocker::Stale); }
+    if live_pool_size.unwrap_or(pool_size) == 0 { return Some(SpinBlocker::EmptyPool); }
+    None
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum Language {
+    #[default]
+    Uk,
+    En,
+}
+
+impl Language {
+    pub fn pick(self, uk: &'static str, en: &'static str) -> &'static str {
+        match self {
+            Language::Uk => uk,
+            Language::En => en,
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Uk => "uk",
+            Language::En => "en",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum Region {
+    #[default]
+    Eu,
+    Na,
+    All,
+}
+
+impl Region {
+    pub fn label(self, lang: Language) -> &'static str {
+        match self {
+            Region::Eu => lang.pick("Європа", "Europe"),
+            Region::Na => lang.pick("Північна Америка", "North America"),
+            Region::All => lang.pick("Усі країни", "All countries"),
+        }
+    }
+
+    /// Whether a server in `country`, an ISO 3166 two-letter code as BattleMetrics gives it, is in the region.
+    pub fn allows(self, country: &str) -> bool {
+        match self {
+            Region::Eu => EU_SET.contains(country),
+            Region::Na => NA_SET.contains(country),
+            Region::All => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct FilterSettings {
+    pub min_players: u32,
+    pub max_players: u32,
+    pub region: Region,
+}
+
+impl Default for FilterSettings {
+    fn default() -> Self {
+        Self { min_players: 60, max_players: 100, region: Region::Eu }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChipKind {
+    Region,
+    Players,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chip {
+    pub kind: ChipKind,
+    pub label: String,
+}
+
+pub fn active_filter_chips(settings: &Settings) -> Vec<Chip> {
+    let filters = &settings.filters;
+    let defaults = FilterSettings::default();
+    let mut chips = Vec::new();
+    if filters.region != defaults.region {
+        chips.push(Chip { kind: ChipKind::Region, label: filters.region.label(settings.language).to_string() });
+    }
+    if (filters.min_players, filters.max_players) != (defaults.min_players, defaults.max_players) {
+        chips.push(Chip { kind: ChipKind::Players, label: format!("👥 {}–{}", filters.min_players, filters.max_players) });
+    }
+    chips
+}
+
+impl FilterSettings {
+    pub fn reset_chip(&mut self, kind: ChipKind) {
+        let defaults = FilterSettings::default();
+        match kind {
+            ChipKind::Region => self.region = defaults.region,
+            ChipKind::Players => {
+                self.min_players = defaults.min_players;
+                self.max_players = defaults.max_players;
+            }
+        }
+    }
+
+    pub fn enforce_player_bounds(&mut self, min_moved: bool) {
+        self.min_players = self.min_players.min(100);
+        self.max_players = self.max_players.min(100);
+        if self.min_players > self.max_players {
+            if min_moved { self.max_players = self.min_players; } else { self.min_players = self.max_players; }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+    System,
+}
+
+impl Theme {
+    pub fn next(self) -> Self {
+        match self {
+            Theme::Dark => Theme::Light,
+            Theme::Light => Theme::System,
+            Theme::System => Theme::Dark,
+        }
+    }
+
+    pub fn icon(self) -> &'static str {
+        match self {
+            Theme::Dark => "🌙",
+            Theme::Light => "☀",
+            Theme::System => "💻",
+        }
+    }
+
+    pub fn label(self, lang: Language) -> &'static str {
+        match self {
+            Theme::Dark => lang.pick("Темна тема", "Dark theme"),
+            Theme::Light => lang.pick("Світла тема", "Light theme"),
+            Theme::System => lang.pick("Як у системі", "Follow system"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AppearanceSettings {
+    pub theme: Theme,
+    pub ui_scale: f32,
+    pub always_on_top: bool,
+    pub minimize_to_tray: bool,
+    pub popout_pos: Option<[f32; 2]>,
+    pub accent: Option<[u8; 3]>,
+    pub row_borders: bool,
+    pub wheel_mode: WheelMode,
+    pub row_size: RowSize,
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        Self { theme: Theme::Dark, ui_scale: 1.0, always_on_top: false, minimize_to_tray: false, popout_pos: None, accent: None, row_borders: true, wheel_mode: WheelMode::default(), row_size: RowSize::default() }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum WheelMode {
+    #[default]
+    Vertical,
+    Horizontal,
+    Circle,
+}
+
+impl WheelMode {
+    pub const ALL: [WheelMode; 3] = [WheelMode::Vertical, WheelMode::Horizontal, WheelMode::Circle];
+
+    pub fn label(self, lang: Language) -> &'static str {
+        match self {
+            WheelMode::Vertical => lang.pick("↕ Вертикальний список", "↕ Vertical list"),
+            WheelMode::Horizontal => lang.pick("↔ Горизонтальна стрічка", "↔ Horizontal strip"),
+            WheelMode::Circle => lang.pick("🎡 Колесо фортуни", "🎡 Wheel of fortune"),
+        }
+    }
+
+    // Distance current_scroll travels per server: all the spin, sound and winner math is in these units.
+    pub fn pitch(self, row_height: f32) -> f32 {
+        match self {
+            WheelMode::Vertical => row_height,
+            WheelMode::Horizontal => CARD_WIDTH,
+            // The disc turns one sector per pitch, so the speed thresholds read the same as for the list.
+            WheelMode::Circle => row_height,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum RowSize {
+    Compact,
+    #[default]
+    Normal,
+    Large,
+}
+
+impl RowSize {
+    pub const ALL: [RowSize; 3] = [RowSize::Compact, RowSize::Normal, RowSize::Large];
+
+    pub fn label(self, lang: Language) -> &'static str {
+        match self {
+            RowSize::Compact => lang.pick("Компактні", "Compact"),
+            RowSize::Normal => lang.pick("Звичайні", "Normal"),
+            RowSize::Large => lang.pick("Великі (для ТБ)", "Large (for TV)"),
+        }
+    }
+
+    pub fn height(self) -> f32 {
+        match self {
+            RowSize::Compact => 48.0,
+            RowSize::Normal => ROW_HEIGHT,
+            RowSize::Large => 120.0,
+        }
+    }
+
+    // Row text follows the row, so a compact row still fits both lines and a large one reads from the sofa.
+    pub fn text_scale(self) -> f32 {
+        self.height() / ROW_HEIGHT
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub sound_enabled: bool,
+    pub volume: u8,
+    // Paths to the user's own sounds; empty means the built-in one.
+    pub tick_file: String,
+    pub win_file: String,
+    pub instant_fanfare: bool,
+    pub countdown_sounds: bool,
+    pub drumroll: bool,
+    pub announce_winner: bool,
+    pub theme: SoundTheme,
+    pub sound_lab: bool,
+    // Set from the sound lab; overrides the theme's click while it's Some.
+    pub click_tuning: Option<ClickParams>,
+    // Output device by name; None follows the system default.
+    pub device: Option<String>,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self { sound_enabled: true, volume: 60, tick_file: String::new(), win_file: String::new(), instant_fanfare: false, countdown_sounds: true, drumroll: false, announce_winner: false, theme: SoundTheme::default(), sound_lab: false, click_tuning: None, device: None }
+    }
+}
+
+impl AudioSettings {
+    pub fn click(&self) -> ClickParams {
+        self.click_tuning.unwrap_or(*self.theme.click())
+    }
+
+    pub fn gain(&self) -> f32 {
+        if self.sound_enabled { self.volume.min(100) as f32 / 100.0 } else { 0.0 }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct AnimationSettings {
+    pub reduced_effects: bool,
+    pub reduced_motion: bool,
+    pub countdown: bool,
+    pub near_miss: bool,
+    pub nudge: bool,
+    pub nudge_percent: u8,
+    pub instant: bool,
+    pub min_duration: f32,
+    pub max_duration: f32,
+    pub easing: Easing,
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self { reduced_effects: false, reduced_motion: false, countdown: false, near_miss: false, nudge: false, nudge_percent: 15, instant: false, min_duration: 10.0, max_duration: 15.0, easing: Easing::default() }
+    }
+}
+
+impl AnimationSettings {
+    pub fn enforce_duration_bounds(&mut self, min_moved: bool) {
+        self.min_duration = self.min_duration.clamp(SPIN_DURATION_MIN, SPIN_DURATION_MAX);
+        self.max_duration = self.max_duration.clamp(SPIN_DURATION_MIN, SPIN_DURATION_MAX);
+        if self.min_duration > self.max_duration {
+            if min_moved { self.max_duration = self.min_duration; } else { self.min_duration = self.max_duration; }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Settings {
+    config_version: u32,
+    pub language: Language,
+    pub onboarding_done: bool,
+    pub filters: FilterSettings,
+    pub audio: AudioSettings,
+    pub animation: AnimationSettings,
+    pub appearance: AppearanceSettings,
+    pub notifications: NotificationSettings,
+    pub discord_template: String,
+    pub last_view: View,
+    pub debug_stats: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            config_version: CONFIG_VERSION,
+            language: Language::default(),
+            onboarding_done: false,
+            filters: FilterSettings::default(),
+            audio: AudioSettings::default(),
+            animation: AnimationSettings::default(),
+            appearance: AppearanceSettings::default(),
+            notifications: NotificationSettings::default(),
+            discord_template: DEFAULT_DISCORD_TEMPLATE.to_string(),
+            last_view: View::default(),
+            debug_stats: false,
+        }
+    }
+}
+
+/// Indices into `raw` of the servers the filters let through, in fetch order.
+pub fn apply_filters(raw: &[ServerItem], filters: &FilterSettings) -> Vec<usize> {
+    raw.iter()
+        .enumerate()
+        .filter(|(_, s)| filters.region.allows(&s.country) && (filters.min_players..=filters.max_players).contains(&s.players))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// How many servers each filter turned away, as (by region, by player count). Region is checked
+/// first, as in apply_filters, so a server both filters reject counts once, against its region.
+pub fn count_exclusions(raw: &[ServerItem], filters: &FilterSettings) -> (usize, usize) {
+    let by_region = raw.iter().filter(|s| !filters.region.allows(&s.country)).count();
+    let by_players = raw.iter()
+        .filter(|s| filters.region.allows(&s.country) && !(filters.min_players..=filters.max_players).contains(&s.players))
+        .count();
+    (by_region, by_players)
+}
+
+/// The servers a spin draws from: what got through the filters, minus blacklisted and session-dropped ones.
+pub fn build_pool(fetched: &[ServerItem], lists: &ServerLists, excluded: &HashSet<String>) -> ServerPool {
+    fetched.iter().filter(|s| !lists.is_blacklisted(&s.id) && !excluded.contains(&s.id)).cloned().collect()
+}
+
+/// Holds pool rebuilds back while the wheel spins, since swapping the rows would move the winner out from
+/// under the pointer, and lets the last one through once the spin ends.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DeferredRebuild {
+    pending: bool,
+}
+
+impl DeferredRebuild {
+    /// Whether to rebuild now; mid-spin the rebuild is remembered for `settle` instead.
+    pub fn request(&mut self, state: RouletteState) -> bool {
+        if state == RouletteState::Spinning { self.pending = true; return false; }
+        true
+    }
+
+    /// Whether a rebuild was held back by the spin that just ended; clears it.
+    pub fn settle(&mut self) -> bool {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// What the spin and the pool UI both derive from the pool, worked out once per pool (and so per filter)
+/// change instead of by each of them, every spin or every frame.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PoolStats {
+    pub servers: usize,
+    // Most common first, then by name.
+    pub countries: Vec<(String, usize)>,
+    pub maps: Vec<(String, usize)>,
+    pub players: u64,
+    pub capacity: u64,
+}
+
+impl PoolStats {
+    pub fn new(pool: &[ServerItem]) -> Self {
+        Self {
+            servers: pool.len(),
+            countries: tally(pool.iter().map(|s| s.country.as_str())),
+            maps: tally(pool.iter().map(|s| s.map.as_str())),
+            players: pool.iter().map(|s| s.players as u64).sum(),
+            capacity: pool.iter().map(|s| s.max_players as u64).sum(),
+        }
+    }
+
+    // Every server has the same chance: one uniform draw, so a seed picks the same winner it always has.
+    pub(crate) fn pick(&self, rng: &mut impl Rng) -> usize {
+        if self.servers == 0 { return 0; }
+        rng.gen_range(0..self.servers)
+    }
+}
+
+fn tally<'a>(values: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for value in values { *counts.entry(value).or_default() += 1; }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().map(|(value, n)| (value.to_string(), n)).collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+// How many of the most common countries and maps the pool breakdown names.
+pub(crate) const BREAKDOWN_TOP: usize = 5;
+
+pub fn sample_servers() -> Vec<ServerItem> {
+    let sample = |id: &str, name: &str, players: u32, map: &str, country: &str| ServerItem {
+        id: id.to_string(),
+        name: name.to_string(),
+        players,
+        max_players: 100,
+        map: map.to_string(),
+        mode: "RAAS".to_string(),
+        country: country.to_string(),
+        queue: players.saturating_sub(95),
+        ip: String::new(),
+        port: 0,
+        rank: None,
+        licensed: true,
+    };
+    vec![
+        sample("sample-1", "=EXD= EXODUS | Friendly Vets | Discord: exodus", 98, "Narva", "DE"),
+        sample("sample-2", "🇺🇦 UA Squad 🔥🔥🔥 Найкращий сервер України 🔥🔥🔥 | Новачкам раді | Регулярні івенти щотижня 🎉🎉🎉 | discord.gg/uasquad 🚀🚀", 87, "Yehorivka", "UA"),
+        sample("sample-3", "[PL] Polska Squad", 72, "Gorodok", "PL"),
+        sample("sample-4", "UK Tactical Gaming", 64, "Fool's Road", "GB"),
+    ]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Population {
+    Sweet,
+    Busy,
+    Full,
+    Low,
+}
+
+impl Population {
+    pub const ALL: [Population; 4] = [Population::Sweet, Population::Busy, Population::Full, Population::Low];
+
+    pub fn label(self, lang: Language) -> &'static str {
+        match self {
+            Population::Sweet => lang.pick("Майже повний", "Almost full"),
+            Population::Busy => lang.pick("Жвавий", "Busy"),
+            Population::Full => lang.pick("Повний / черга", "Full / queue"),
+            Population::Low => lang.pick("Малолюдний", "Quiet"),
+        }
+    }
+
+    pub fn of(server: &ServerItem) -> Self {
+        let per_hundred = server.players * 100 / server.max_players.max(1);
+        if server.queue > 0 || per_hundred > POPULATION_SWEET_MAX { Population::Full }
+        else if per_hundred >= POPULATION_SWEET_MIN { Population::Sweet }
+        else if per_hundred >= POPULATION_BUSY_MIN { Population::Busy }
+        else { Population::Low }
+    }
+}
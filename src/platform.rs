@@ -0,0 +1,24 @@
+use std::thread;
+
+use eframe::egui;
+use tracing::debug;
+
+use squad_roulette::storage::APP_NAME;
+
+pub(crate) fn always_on_top_supported() -> bool {
+    // winit ignores window levels on Wayland, so the request would silently do nothing there.
+    !(cfg!(target_os = "linux") && std::env::var_os("WAYLAND_DISPLAY").is_some())
+}
+
+pub(crate) fn apply_window_level(ctx: &egui::Context, always_on_top: bool) {
+    let level = if always_on_top { egui::WindowLevel::AlwaysOnTop } else { egui::WindowLevel::Normal };
+    ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
+}
+
+pub(crate) fn send_desktop_notification(body: String) {
+    thread::spawn(move || {
+        if let Err(e) = notify_rust::Notification::new().appname(APP_NAME).summary(APP_NAME).body(&body).show() {
+            debug!("desktop notification unavailable: {}", e);
+        }
+    });
+}
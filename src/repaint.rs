@@ -0,0 +1,73 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::alloc_counter::allocations;
+
+// Features say when they next need a frame; update asks egui once, for the earliest of them. Without it
+// every label that changes once a minute would be tempted to keep the app redrawing at full rate.
+// Requests only need `&self`, so drawing code that can't borrow the app mutably can still ask.
+#[derive(Default)]
+pub struct RepaintScheduler {
+    deadline: Cell<Option<Instant>>,
+    // Frames and wakeups (frames no input caused) counted over the current second, and the last full second's totals.
+    window_start: Option<Instant>,
+    frames: u32,
+    wakeups: u32,
+    frames_per_sec: u32,
+    wakeups_per_sec: u32,
+    // Only known in builds with the alloc-counter feature.
+    window_allocations: Option<u64>,
+    allocations_per_sec: Option<u64>,
+}
+
+impl RepaintScheduler {
+    // For animations: another frame as soon as possible.
+    pub fn request_now(&self) {
+        self.request_after(Duration::ZERO);
+    }
+
+    pub fn request_after(&self, delay: Duration) {
+        let at = Instant::now() + delay;
+        if self.deadline.get().is_none_or(|deadline| at < deadline) { self.deadline.set(Some(at)); }
+    }
+
+    pub fn begin_frame(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        let start = *self.window_start.get_or_insert_with(|| {
+            self.window_allocations = allocations();
+            now
+        });
+        if now.duration_since(start) >= Duration::from_secs(1) {
+            let allocated = allocations();
+            self.frames_per_sec = self.frames;
+            self.wakeups_per_sec = self.wakeups;
+            self.allocations_per_sec = allocated.zip(self.window_allocations).map(|(now, then)| now.saturating_sub(then));
+            self.frames = 0;
+            self.wakeups = 0;
+            self.window_start = Some(now);
+            self.window_allocations = allocated;
+        }
+        self.frames += 1;
+        if ctx.input(|i| i.events.is_empty() && i.pointer.delta() == egui::Vec2::ZERO) { self.wakeups += 1; }
+    }
+
+    // The one place a repaint gets requested from the UI thread.
+    pub fn finish_frame(&self, ctx: &egui::Context) {
+        let Some(deadline) = self.deadline.take() else { return; };
+        ctx.request_repaint_after(deadline.saturating_duration_since(Instant::now()));
+    }
+
+    pub fn frames_per_sec(&self) -> u32 {
+        self.frames_per_sec
+    }
+
+    pub fn wakeups_per_sec(&self) -> u32 {
+        self.wakeups_per_sec
+    }
+
+    pub fn allocations_per_sec(&self) -> Option<u64> {
+        self.allocations_per_sec
+    }
+}
@@ -0,0 +1,66 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::animation::Easing;
+use crate::model::{AnimationSettings, PoolStats};
+
+// The pointer sits on the item for round(scroll / pitch), so the landing spot must stay well inside ±0.5 of a row.
+const MAX_LANDING_OFFSET: f32 = 0.35;
+const CROSSFADE_NAMES: usize = 10;
+
+/// Everything random about a spin, drawn up front from one seed; the animation only plays it back.
+/// Offsets and positions are in pitches, so a recorded spin replays in any wheel layout.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SpinOutcome {
+    pub seed: u64,
+    pub winner_idx: usize,
+    pub duration: f32,
+    pub offset: f32,
+    pub start_slot: f32,
+    pub easing: Easing,
+    pub near_miss: bool,
+    // The wheel stops a row short and creeps onto the winner; winner_idx is still where it ends up.
+    pub nudge: bool,
+}
+
+/// The names a reduced-motion spin fades through, ending on the winner. Drawn from the spin's own seed
+/// so a replay shows the same sequence.
+pub fn crossfade_names(outcome: &SpinOutcome, pool_len: usize) -> Vec<usize> {
+    if pool_len < 2 { return vec![outcome.winner_idx]; }
+    let mut rng = StdRng::seed_from_u64(outcome.seed.rotate_left(32));
+    // Built backwards from the winner so every name differs from the one after it, the final fade included.
+    let mut names: Vec<usize> = Vec::with_capacity(CROSSFADE_NAMES);
+    names.push(outcome.winner_idx);
+    for _ in 1..CROSSFADE_NAMES {
+        let next = names[names.len() - 1];
+        let mut idx = rng.gen_range(0..pool_len - 1);
+        if idx >= next { idx += 1; }
+        names.push(idx);
+    }
+    names.reverse();
+    names
+}
+
+/// The winner `seed` draws from the pool. Quick picks, instant spins and animated spins all come
+/// through here, so one seed names the same server whichever way it is shown.
+pub fn pick_winner(seed: u64, stats: &PoolStats) -> usize {
+    stats.pick(&mut StdRng::seed_from_u64(seed))
+}
+
+/// Draws the winner and everything else about the spin from `seed`, so the same seed and pool always
+/// give the same spin.
+pub fn choose_outcome(seed: u64, stats: &PoolStats, start_slot: f32, animation: &AnimationSettings) -> SpinOutcome {
+    // A stream of its own, so the look of the spin never shifts which server the seed picks.
+    let mut rng = StdRng::seed_from_u64(seed.rotate_left(16));
+    SpinOutcome {
+        seed,
+        winner_idx: pick_winner(seed, stats),
+        duration: rng.gen_range(animation.min_duration..=animation.max_duration),
+        offset: rng.gen_range(-MAX_LANDING_OFFSET..MAX_LANDING_OFFSET),
+        start_slot,
+        easing: animation.easing,
+        near_miss: animation.near_miss,
+        nudge: animation.nudge && rng.gen_ratio(animation.nudge_percent.min(100) as u32, 100),
+    }
+}
@@ -0,0 +1,121 @@
+use crate::model::ServerItem;
+
+// The last fetched server list, kept between runs so the wheel isn't empty at startup. JSON for 500
+// servers is slow to re-parse on every launch, so this is a compact little-endian binary layout:
+//
+//   magic "SQRS" · format version (1 byte) · saved at (u64 unix secs) · count (u32) · servers · CRC-32 (u32)
+//
+// The checksum covers everything before it. Any mismatch or short read is an error, and the caller
+// simply refetches; the file is only a cache. Written out by hand rather than with a serde binary
+// format: one flat record type doesn't need one, and this way the layout is pinned down right here.
+const MAGIC: &[u8; 4] = b"SQRS";
+const FORMAT_VERSION: u8 = 1;
+
+pub struct Snapshot {
+    pub saved_at: u64,
+    pub servers: Vec<ServerItem>,
+}
+
+pub fn encode(servers: &[ServerItem], saved_at: u64) -> Vec<u8> {
+    let mut out = Vec::with_capacity(17 + servers.len() * 96);
+    out.extend_from_slice(MAGIC);
+    out.push(FORMAT_VERSION);
+    out.extend_from_slice(&saved_at.to_le_bytes());
+    put_u32(&mut out, servers.len() as u32);
+    for server in servers {
+        put_str(&mut out, &server.id);
+        put_str(&mut out, &server.name);
+        put_u32(&mut out, server.players);
+        put_u32(&mut out, server.max_players);
+        put_str(&mut out, &server.map);
+        put_str(&mut out, &server.mode);
+        put_str(&mut out, &server.country);
+        put_u32(&mut out, server.queue);
+        put_str(&mut out, &server.ip);
+        put_u32(&mut out, server.port);
+        match server.rank {
+            Some(rank) => {
+                out.push(1);
+                put_u32(&mut out, rank);
+            }
+            None => out.push(0),
+        }
+        out.push(server.licensed as u8);
+    }
+    let checksum = crc32fast::hash(&out);
+    put_u32(&mut out, checksum);
+    out
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Snapshot, String> {
+    let Some((body, checksum)) = bytes.split_last_chunk::<4>() else { return Err("file too short".to_string()); };
+    if crc32fast::hash(body) != u32::from_le_bytes(*checksum) { return Err("checksum mismatch".to_string()); }
+    let mut reader = Reader { bytes: body };
+    if reader.take(MAGIC.len())? != MAGIC { return Err("not a server snapshot".to_string()); }
+    let version = reader.u8()?;
+    if version != FORMAT_VERSION { return Err(format!("unsupported snapshot version {}", version)); }
+    let saved_at = u64::from_le_bytes(reader.array()?);
+    let count = reader.u32()? as usize;
+    // Every server takes at least this many bytes, so a corrupt count can't reserve gigabytes.
+    let mut servers = Vec::with_capacity(count.min(reader.bytes.len() / 42));
+    for _ in 0..count {
+        servers.push(ServerItem {
+            id: reader.string()?,
+            name: reader.string()?,
+            players: reader.u32()?,
+            max_players: reader.u32()?,
+            map: reader.string()?,
+            mode: reader.string()?,
+            country: reader.string()?,
+            queue: reader.u32()?,
+            ip: reader.string()?,
+            port: reader.u32()?,
+            rank: match reader.u8()? {
+                0 => None,
+                _ => Some(reader.u32()?),
+            },
+            licensed: reader.u8()? != 0,
+        });
+    }
+    if !reader.bytes.is_empty() { return Err(format!("{} unexpected bytes after the last server", reader.bytes.len())); }
+    Ok(Snapshot { saved_at, servers })
+}
+
+fn put_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn put_str(out: &mut Vec<u8>, value: &str) {
+    put_u32(out, value.len() as u32);
+    out.extend_from_slice(value.as_bytes());
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() < len { return Err("unexpected end of snapshot".to_string()); }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.take(N)?.try_into().expect("take returns exactly N bytes"))
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,132 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, Sender};
+use std::thread;
+
+use tracing::{debug, warn};
+
+// The platform's own speech tool; which one is found once at startup.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Backend {
+    SpeechDispatcher,
+    Espeak(&'static str),
+    Say,
+    WindowsSpeech,
+}
+
+// One phrase to read: the text and a two-letter language code.
+type Phrase = (String, &'static str);
+
+// Announcements go to a single speech thread. A phrase that's still waiting when a newer one arrives is
+// dropped, so quick spins in a row don't leave a backlog of winners being read out long after.
+pub struct Speaker {
+    phrases: Option<Sender<Phrase>>,
+}
+
+impl Speaker {
+    pub fn new() -> Self {
+        let backend = if cfg!(target_os = "windows") {
+            Some(Backend::WindowsSpeech)
+        } else if cfg!(target_os = "macos") {
+            on_path("say").then_some(Backend::Say)
+        } else if on_path("spd-say") {
+            Some(Backend::SpeechDispatcher)
+        } else {
+            ["espeak-ng", "espeak"].into_iter().find(|name| on_path(name)).map(Backend::Espeak)
+        };
+        debug!("text-to-speech backend: {:?}", backend);
+        let phrases = backend.and_then(|backend| {
+            let (phrases, queue) = channel::<Phrase>();
+            thread::Builder::new()
+                .name("speech".into())
+                .spawn(move || {
+                    while let Ok(mut phrase) = queue.recv() {
+                        if let Some(newer) = queue.try_iter().last() { phrase = newer; }
+                        say(backend, &phrase.0, phrase.1);
+                    }
+                })
+                .map_err(|e| warn!("failed to start the speech thread: {}", e))
+                .ok()
+                .map(|_| phrases)
+        });
+        Self { phrases }
+    }
+
+    pub fn available(&self) -> bool {
+        self.phrases.is_some()
+    }
+
+    // `language` is a two-letter code. Speaking can take seconds, so this only queues the phrase.
+    pub fn speak(&self, text: String, language: &'static str) {
+        let Some(phrases) = &self.phrases else { return; };
+        let _ = phrases.send((text, language));
+    }
+}
+
+// Blocks until the phrase has been read out, or the tool has failed.
+fn say(backend: Backend, text: &str, language: &str) {
+    let mut command = match backend {
+        Backend::SpeechDispatcher => {
+            let mut command = Command::new("spd-say");
+            // --wait keeps spd-say running until it's done speaking, so it can report a failure and
+            // the next phrase doesn't cut in.
+            command.args(["--wait", "-l", language, "--"]).arg(text);
+            command
+        }
+        Backend::Espeak(program) => {
+            let mut command = Command::new(program);
+            command.args(["-v", language, "--"]).arg(text);
+            command
+        }
+        // `say` has no language switch; it reads with the voice picked in the system settings.
+        Backend::Say => {
+            let mut command = Command::new("say");
+            command.arg("--").arg(text);
+            command
+        }
+        // The text goes in through the environment, so nothing in a server name is parsed as script.
+        Backend::WindowsSpeech => {
+            let mut command = Command::new("powershell");
+            command.args([
+                "-NoProfile",
+                "-NonInteractive",
+                "-Command",
+                "Add-Type -AssemblyName System.Speech; \
+                 $s = New-Object System.Speech.Synthesis.SpeechSynthesizer; \
+                 try { $s.SelectVoiceByHints('NotSet', 'NotSet', 0, [Globalization.CultureInfo]$env:SQUAD_ROULETTE_LANG) } catch {}; \
+                 $s.Speak($env:SQUAD_ROULETTE_SPEECH)",
+            ]);
+            command.env("SQUAD_ROULETTE_SPEECH", text).env("SQUAD_ROULETTE_LANG", language);
+            command
+        }
+    };
+    match command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status() {
+        Ok(status) if !status.success() => debug!("text-to-speech exited with {}", status),
+        Ok(_) => {}
+        Err(e) => debug!("text-to-speech failed to start: {}", e),
+    }
+}
+
+impl Default for Speaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Server names are full of stars, flags, pipes and brackets; read aloud they're noise, so only letters,
+// digits and ordinary punctuation survive, with the gaps they leave squeezed into single spaces.
+pub fn speakable(text: &str) -> String {
+    let kept: String = text
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | ',' | '-' | '\'' | '&' | ':') { c } else { ' ' })
+        .collect();
+    kept.split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn on_path(program: &str) -> bool {
+    let Some(paths) = std::env::var_os("PATH") else { return false; };
+    std::env::split_paths(&paths).any(|dir| Path::new(&dir).join(program).is_file())
+}
@@ -0,0 +1,175 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::model::{ServerLists, Settings, CONFIG_VERSION};
+
+pub const APP_NAME: &str = "Squad EU Roulette";
+const SETTINGS_FILE: &str = "settings.json";
+const LISTS_FILE: &str = "lists.json";
+
+static DATA_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+// Where the platform keeps app data is the window layer's business, so the binary hands the directory
+// over once at startup; until then nothing is read or written.
+pub fn set_data_dir(dir: Option<PathBuf>) {
+    if DATA_DIR.set(dir).is_err() { warn!("data directory is already set, keeping the first one"); }
+}
+
+pub fn data_dir() -> Option<PathBuf> {
+    DATA_DIR.get().cloned().flatten()
+}
+
+fn save_json_atomic<T: Serialize>(path: &Path, value: &T) -> std::io::Result<()> {
+    let text = serde_json::to_vec_pretty(value).map_err(std::io::Error::other)?;
+    write_atomic(path, &text)
+}
+
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(dir)?;
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("data");
+    let tmp_path = dir.join(format!(".{}.tmp", file_name));
+    {
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+    }
+    std::fs::rename(&tmp_path, path)
+}
+
+pub fn load_json_or_default<T: DeserializeOwned + Default>(path: &Path) -> T {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound { warn!("failed to read {}: {}", path.display(), e); }
+            return T::default();
+        }
+    };
+    match serde_json::from_slice(&bytes) {
+        Ok(value) => value,
+        Err(e) => {
+            move_aside(path, &e.to_string());
+            T::default()
+        }
+    }
+}
+
+// Keeps a file that can't be used out of the way of the defaults about to be saved over it.
+fn move_aside(path: &Path, reason: &str) {
+    let secs = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let aside = path.with_extension(format!("corrupt-{}.json", secs));
+    warn!("{} is corrupt ({}), moving it to {} and using defaults", path.display(), reason, aside.display());
+    if let Err(e) = std::fs::rename(path, &aside) { warn!("failed to move corrupt file aside: {}", e); }
+}
+
+fn migrate_v1_to_v2(mut value: serde_json::Value) -> serde_json::Value {
+    value["config_version"] = serde_json::json!(2);
+    value
+}
+
+fn migrate_v2_to_v3(mut value: serde_json::Value) -> serde_json::Value {
+    value["config_version"] = serde_json::json!(3);
+    value["onboarding_done"] = serde_json::json!(true);
+    value
+}
+
+fn migrate_settings(mut value: serde_json::Value) -> Result<serde_json::Value, u32> {
+    let mut version = value.get("config_version").and_then(|v| v.as_u64()).map(|v| v as u32).unwrap_or(1);
+    if version > CONFIG_VERSION { return Err(version); }
+    while version < CONFIG_VERSION {
+        value = match version {
+            1 => migrate_v1_to_v2(value),
+            2 => migrate_v2_to_v3(value),
+            _ => unreachable!("missing settings migration from v{}", version),
+        };
+        info!("migrated settings from v{} to v{}", version, version + 1);
+        version += 1;
+    }
+    Ok(value)
+}
+
+// Files load_json_or_default and load_settings moved out of the way are left for the user to inspect,
+// but only the newest of them, up to `max_bytes` together; the rest are deleted.
+pub fn prune_aside_files(dir: &Path, max_bytes: u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return; };
+    let mut aside: Vec<(std::time::SystemTime, u64, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.ends_with(".json") && (name.contains(".corrupt-") || name.starts_with("settings.v"))
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((metadata.modified().unwrap_or(std::time::UNIX_EPOCH), metadata.len(), entry.path()))
+        })
+        .collect();
+    aside.sort_by_key(|(modified, _, _)| std::cmp::Reverse(*modified));
+    let mut total = 0;
+    for (_, len, path) in aside {
+        total += len;
+        if total <= max_bytes { continue; }
+        match std::fs::remove_file(&path) {
+            Ok(()) => info!("removed old {} to keep set-aside files under {} bytes", path.display(), max_bytes),
+            Err(e) => warn!("failed to remove {}: {}", path.display(), e),
+        }
+    }
+}
+
+pub fn load_settings() -> (Settings, Option<String>) {
+    match data_dir() {
+        Some(dir) => load_settings_from(&dir.join(SETTINGS_FILE)),
+        None => (Settings::default(), None),
+    }
+}
+
+/// Reads the settings at `path`, migrating older versions. Nothing the user saved is ever lost to the
+/// defaults: a file from a newer version is backed up as `settings.v<N>.json` (and the returned warning
+/// says so), and one that no longer fits the settings is moved aside like a corrupt file.
+pub fn load_settings_from(path: &Path) -> (Settings, Option<String>) {
+    let value: serde_json::Value = load_json_or_default(path);
+    if value.is_null() { return (Settings::default(), None); }
+    // The migrations index into the top-level object; valid JSON of any other shape is as good as corrupt.
+    if !value.is_object() {
+        move_aside(path, "not a JSON object");
+        return (Settings::default(), None);
+    }
+    match migrate_settings(value) {
+        Ok(value) => match serde_json::from_value::<Settings>(value) {
+            Ok(mut settings) => {
+                settings.filters.enforce_player_bounds(true);
+                settings.animation.enforce_duration_bounds(true);
+                (settings, None)
+            }
+            Err(e) => {
+                move_aside(path, &e.to_string());
+                (Settings::default(), None)
+            }
+        },
+        Err(version) => {
+            let backup = path.with_extension(format!("v{}.json", version));
+            warn!("settings file is from a newer version (v{}), backing it up to {}", version, backup.display());
+            if let Err(e) = std::fs::rename(path, &backup) { warn!("failed to back up settings: {}", e); }
+            let warning = format!("Налаштування з новішої версії (v{}) збережено як {} — використано типові.", version, backup.display());
+            (Settings::default(), Some(warning))
+        }
+    }
+}
+
+pub fn load_lists() -> ServerLists {
+    data_dir().map(|dir| load_json_or_default(&dir.join(LISTS_FILE))).unwrap_or_default()
+}
+
+pub fn save_lists(lists: &ServerLists) {
+    let Some(dir) = data_dir() else { return; };
+    if let Err(e) = save_json_atomic(&dir.join(LISTS_FILE), lists) { warn!("failed to save server lists: {}", e); }
+}
+
+pub fn save_settings(settings: &Settings) {
+    let Some(dir) = data_dir() else { return; };
+    if let Err(e) = save_json_atomic(&dir.join(SETTINGS_FILE), settings) { warn!("failed to save settings: {}", e); }
+}
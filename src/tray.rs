@@ -0,0 +1,95 @@
+// The system tray icon, only built with the tray feature. Menu clicks arrive as TrayCommands on the
+// handle's channel, and update handles them like any other input.
+
+use std::sync::mpsc::{channel, Receiver};
+#[cfg(target_os = "linux")]
+use std::thread;
+
+use eframe::egui;
+use tracing::warn;
+
+use squad_roulette::model::Language;
+use squad_roulette::storage::APP_NAME;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum TrayCommand {
+    ToggleWindow,
+    Refresh,
+    Spin,
+    Quit,
+}
+
+pub(crate) struct TrayHandle {
+    pub(crate) rx: Receiver<TrayCommand>,
+    _icon: Option<tray_icon::TrayIcon>,
+}
+
+fn tray_icon_image() -> Result<tray_icon::Icon, String> {
+    const SIZE: u32 = 32;
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let (dx, dy) = (x as f32 - 15.5, y as f32 - 15.5);
+            let alpha = if dx * dx + dy * dy <= 15.0 * 15.0 { 255 } else { 0 };
+            rgba.extend_from_slice(&[255, 215, 0, alpha]);
+        }
+    }
+    tray_icon::Icon::from_rgba(rgba, SIZE, SIZE).map_err(|e| e.to_string())
+}
+
+fn build_tray_icon(lang: Language) -> Result<tray_icon::TrayIcon, String> {
+    use tray_icon::menu::{Menu, MenuItem};
+    let menu = Menu::new();
+    let show = MenuItem::with_id("show", lang.pick("Показати / сховати", "Show / Hide"), true, None);
+    let refresh = MenuItem::with_id("refresh", lang.pick("Оновити", "Refresh"), true, None);
+    let spin = MenuItem::with_id("spin", lang.pick("Крутити", "Spin"), true, None);
+    let quit = MenuItem::with_id("quit", lang.pick("Вийти", "Quit"), true, None);
+    menu.append_items(&[&show, &refresh, &spin, &quit]).map_err(|e| e.to_string())?;
+    tray_icon::TrayIconBuilder::new()
+        .with_menu(Box::new(menu))
+        .with_tooltip(APP_NAME)
+        .with_icon(tray_icon_image()?)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+pub(crate) fn spawn_tray(ctx: egui::Context, lang: Language) -> Option<TrayHandle> {
+    let (tx, rx) = channel();
+    tray_icon::menu::MenuEvent::set_event_handler(Some(move |event: tray_icon::menu::MenuEvent| {
+        let command = match event.id.0.as_str() {
+            "show" => TrayCommand::ToggleWindow,
+            "refresh" => TrayCommand::Refresh,
+            "spin" => TrayCommand::Spin,
+            "quit" => TrayCommand::Quit,
+            _ => return,
+        };
+        let _ = tx.send(command);
+        ctx.request_repaint();
+    }));
+
+    // gtk needs its own event loop on the thread that owns the icon; elsewhere the winit loop on the main thread is enough.
+    #[cfg(target_os = "linux")]
+    {
+        thread::spawn(move || {
+            if let Err(e) = gtk::init() {
+                warn!("tray unavailable, gtk failed to initialize: {}", e);
+                return;
+            }
+            match build_tray_icon(lang) {
+                Ok(_icon) => gtk::main(),
+                Err(e) => warn!("failed to create tray icon: {}", e),
+            }
+        });
+        Some(TrayHandle { rx, _icon: None })
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        match build_tray_icon(lang) {
+            Ok(icon) => Some(TrayHandle { rx, _icon: Some(icon) }),
+            Err(e) => {
+                warn!("failed to create tray icon: {}", e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+// Drawing only. theme and widgets are free functions shared by the views; the views themselves are
+// `impl RouletteApp` blocks, one file per screen.
+
+mod roulette;
+mod settings;
+mod tables;
+pub(crate) mod theme;
+pub(crate) mod widgets;
@@ -0,0 +1,1010 @@
+use std::time::Duration;
+
+use eframe::egui;
+use tracing::Level;
+
+use squad_roulette::animation::{server_at, SMALL_POOL_MAX};
+use squad_roulette::format::{ago_bucket, format_ago};
+use squad_roulette::model::{
+    active_filter_chips, sample_servers, Language, Population, Region, RouletteState, View, WheelMode,
+    CARD_WIDTH,
+};
+use squad_roulette::storage::{save_settings, APP_NAME};
+
+use crate::app::{
+    EmptyAction, RouletteApp, RowAction, CONFETTI_LIFETIME, MIN_HIT_SIZE,
+    PERF_SAMPLE_FRAMES, UI_SCALE_MAX, UI_SCALE_MIN,
+};
+use crate::logging::{LogLine, LOG_LINES};
+use crate::platform::{always_on_top_supported, apply_window_level};
+use crate::ui::theme::{accent_color, apply_theme, contrasting_text, palette};
+use crate::ui::widgets::{
+    focus_emphasis, gated_button, icon_button, paint_edge_fade, paint_loading_rows,
+    paint_replay_watermark, paint_winner_glow, row_context_menu, server_details_grid, slider_with_value,
+    wheel_card_ui, wheel_row_ui, winner_card_ui, winner_strip_ui, LayoutMetrics, RowDetail, RowLook,
+    FOCUS_MAX_SCALE,
+};
+
+const CARD_HEIGHT: f32 = 110.0;
+const WHEEL_LABEL_MAX: usize = 40;
+const WHEEL_SEGMENTS: usize = 180;
+const MOTION_FADE_SPEED: f32 = 2400.0;
+const TOAST_TIME: f32 = 8.0;
+const PRESENTATION_TEXT_SCALE: f32 = 1.5;
+
+impl RouletteApp {
+    fn pin_button_ui(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        let supported = always_on_top_supported();
+        let pinned = self.settings.appearance.always_on_top;
+        let rect = egui::Rect::from_min_size(egui::pos2(ui.max_rect().right() - 32.0, ui.max_rect().top()), egui::vec2(32.0, 32.0));
+        let button = egui::SelectableLabel::new(pinned, egui::RichText::new("📌").size(18.0));
+        let response = ui.put(rect, button);
+        response.widget_info(|| egui::WidgetInfo::selected(egui::WidgetType::Button, supported, pinned, lang.pick("Поверх інших вікон", "Always on top")));
+        let response = if supported {
+            response.on_hover_text(if pinned { lang.pick("Відкріпити вікно", "Unpin the window") } else { lang.pick("Закріпити поверх інших вікон", "Keep the window on top") })
+        } else {
+            response.on_hover_text(lang.pick("Закріплення поверх вікон не підтримується цією системою (Wayland)", "Always-on-top is not supported on this system (Wayland)"))
+        };
+        if response.clicked() {
+            if supported {
+                self.settings.appearance.always_on_top = !pinned;
+                apply_window_level(ui.ctx(), !pinned);
+            } else {
+                self.settings.appearance.always_on_top = false;
+            }
+            save_settings(&self.settings);
+        }
+    }
+
+    fn status_bar_ui(&mut self, ctx: &egui::Context) {
+        let lang = self.settings.language;
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            let colors = palette(ui.visuals());
+            ui.horizontal(|ui| {
+                let (dot, status) = match self.fetch_summary {
+                    _ if self.roulette_state == RouletteState::Loading => (colors.warning, lang.pick("Запит до BattleMetrics…", "Querying BattleMetrics…")),
+                    None => (ui.visuals().weak_text_color(), lang.pick("API ще не перевірено", "API not checked yet")),
+                    Some(s) if s.failed => (colors.danger, lang.pick("BattleMetrics недоступний", "BattleMetrics unreachable")),
+                    Some(s) if s.partial => (colors.warning, lang.pick("BattleMetrics відповів частково", "BattleMetrics answered partially")),
+                    Some(_) => (colors.ok, lang.pick("BattleMetrics доступний", "BattleMetrics reachable")),
+                };
+                let (rect, response) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+                ui.painter().circle_filled(rect.center(), 4.0, dot);
+                response.on_hover_text(status);
+                ui.separator();
+
+                let state = match self.roulette_state {
+                    RouletteState::Ready => lang.pick("Готово", "Ready").to_string(),
+                    RouletteState::Loading => lang.pick("Завантаження…", "Loading…").to_string(),
+                    RouletteState::Countdown => lang.pick("Відлік…", "Counting down…").to_string(),
+                    RouletteState::Spinning => lang.pick("Крутиться…", "Spinning…").to_string(),
+                    RouletteState::Finished => match &self.selected_server {
+                        Some(winner) => format!("{} {}", lang.pick("Переможець:", "Winner:"), winner.name),
+                        None => lang.pick("Немає серверів", "No servers").to_string(),
+                    },
+                };
+                let state_label = ui.scope(|ui| {
+                    ui.set_max_width(220.0);
+                    ui.add(egui::Label::new(egui::RichText::new(state).small()).truncate())
+                }).inner;
+                ctx.accesskit_node_builder(state_label.id, |node| node.set_live(egui::accesskit::Live::Polite));
+                ui.separator();
+
+                // Rebuilt only when the wording would change, about once a minute, instead of every frame.
+                let freshness_for = (self.last_refresh, self.last_refresh.map_or(0, ago_bucket), lang);
+                if self.freshness.0 != Some(freshness_for) {
+                    let text = match self.last_refresh {
+                        Some(ts) => format!("{} {}", lang.pick("Оновлено", "Refreshed"), format_ago(ts, lang)),
+                        None => lang.pick("Ще не оновлено", "Not refreshed yet").to_string(),
+                    };
+                    self.freshness = (Some(freshness_for), text);
+                }
+                ui.label(egui::RichText::new(&self.freshness.1).small()).on_hover_text(lang.pick("Час останнього успішного оновлення", "Time of the last successful refresh"));
+                ui.separator();
+                // Hover texts that need formatting are built in on_hover_ui, so only when the tooltip actually shows.
+                ui.label(egui::RichText::new(&self.pool_header).small())
+                    .on_hover_ui(|ui| { ui.label(format!("{} {} · {} {}", lang.pick("Отримано", "Fetched"), self.fetched_servers.len(), lang.pick("у пулі", "in pool"), self.roulette_servers.len())); });
+
+                if self.audio.lost() {
+                    ui.separator();
+                    ui.colored_label(colors.warning, egui::RichText::new(lang.pick("🔇 Аудіопристрій втрачено — перепідключення…", "🔇 Audio device lost — reconnecting…")).small())
+                        .on_hover_text(lang.pick("Якщо звук не повернеться, спробуйте «Перезапустити звук» у налаштуваннях", "If the sound doesn't come back, try \"Reinitialize audio\" in the settings"));
+                } else if !self.audio.available() && !self.audio.initializing() {
+                    ui.separator();
+                    ui.colored_label(colors.warning, egui::RichText::new(lang.pick("🔇 Звук недоступний", "🔇 Audio unavailable")).small())
+                        .on_hover_text(lang.pick("Не вдалося відкрити жоден аудіопристрій; спробуйте «Перезапустити звук» у налаштуваннях", "No audio device could be opened; try \"Reinitialize audio\" in the settings"));
+                }
+
+                if let Some(summary) = self.fetch_summary {
+                    if summary.rate_limited {
+                        ui.separator();
+                        ui.colored_label(colors.warning, egui::RichText::new(lang.pick("⚠ Ліміт запитів", "⚠ Rate limited")).small())
+                            .on_hover_text(lang.pick("BattleMetrics обмежив кількість запитів (HTTP 429). Зачекайте хвилину.", "BattleMetrics rate-limited the requests (HTTP 429). Wait a minute."));
+                    } else if summary.partial {
+                        ui.separator();
+                        ui.colored_label(colors.warning, egui::RichText::new(lang.pick("⚠ Неповні дані", "⚠ Partial data")).small())
+                            .on_hover_ui(|ui| {
+                                let detail = summary.http_status.map(|code| format!("HTTP {}", code)).unwrap_or_else(|| lang.pick("помилка мережі або формату", "network or format error").to_string());
+                                ui.label(format!("{} ({})", lang.pick("Не всі сторінки завантажено", "Not all pages were loaded"), detail));
+                            });
+                    }
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    ui.label(egui::RichText::new(concat!("v", env!("CARGO_PKG_VERSION"))).small().weak())
+                        .on_hover_ui(|ui| { ui.label(format!("{} v{}", APP_NAME, env!("CARGO_PKG_VERSION"))); });
+                    if self.settings.debug_stats {
+                        let mut stats = format!("{} fps · {} wakeups/s", self.repaint.frames_per_sec(), self.repaint.wakeups_per_sec());
+                        if let Some(allocations) = self.repaint.allocations_per_sec() { stats.push_str(&format!(" · {} allocs/s", allocations)); }
+                        ui.label(egui::RichText::new(stats).small().monospace()).on_hover_ui(|ui| {
+                            ui.label(lang.pick("Кадри за секунду; пробудження — кадри без вводу користувача", "Frames per second; wakeups are frames no input caused"));
+                            ui.separator();
+                            ui.label(egui::RichText::new(self.memory_stats(lang)).small().monospace());
+                        });
+                    }
+                });
+            });
+        });
+        if self.last_refresh.is_some() { self.repaint.request_after(Duration::from_secs(30)); }
+    }
+
+    fn pool_preview_ui(&mut self, ui: &mut egui::Ui) {
+        if self.roulette_servers.is_empty() { return; }
+        let lang = self.settings.language;
+        let colors = palette(ui.visuals());
+        let can_edit = self.roulette_state != RouletteState::Spinning;
+        let mut excluded = None;
+        egui::CollapsingHeader::new(self.pool_header.as_str())
+            .id_salt("pool_preview")
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new(&self.pool_breakdown).small().weak());
+                egui::ScrollArea::vertical().id_salt("pool_preview_scroll").max_height(150.0).show(ui, |ui| {
+                    ui.style_mut().spacing.item_spacing.y = 2.0;
+                    for (server, details) in self.roulette_servers.iter().zip(&self.pool_details) {
+                        ui.horizontal(|ui| {
+                            if icon_button(ui, can_edit, "✖", lang.pick("Прибрати з пулу на цю сесію", "Drop from the pool for this session")).clicked() {
+                                excluded = Some(server.id.clone());
+                            }
+                            ui.label(egui::RichText::new(format!("{}/{}", server.players, server.max_players)).color(colors.players).monospace());
+                            ui.add(egui::Label::new(&details.title).truncate()).on_hover_text(&details.country);
+                        });
+                    }
+                });
+                if !self.session_excluded.is_empty() {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(format!("{} {}", lang.pick("Прибрано:", "Dropped:"), self.session_excluded.len())).weak());
+                        if ui.add_enabled(can_edit, egui::Button::new(lang.pick("Повернути всі", "Restore all"))).clicked() {
+                            self.session_excluded.clear();
+                            self.rebuild_pool();
+                        }
+                    });
+                }
+            });
+        if let Some(id) = excluded {
+            self.session_excluded.insert(id);
+            self.rebuild_pool();
+        }
+    }
+
+    fn toasts_ui(&mut self, ctx: &egui::Context) {
+        self.toasts.retain(|t| t.created_at.elapsed().as_secs_f32() < TOAST_TIME);
+        if self.toasts.is_empty() { return; }
+        let lang = self.settings.language;
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -40.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (i, toast) in self.toasts.iter().enumerate() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.set_max_width(320.0);
+                        ui.label(&toast.text);
+                        ui.horizontal(|ui| {
+                            if let Some(copy) = &toast.copy {
+                                if ui.button(lang.pick("📋 Копіювати", "📋 Copy")).clicked() { ctx.output_mut(|o| o.copied_text = copy.clone()); }
+                            }
+                            if icon_button(ui, true, "✖", lang.pick("Закрити", "Dismiss")).clicked() { dismissed = Some(i); }
+                        });
+                    });
+                }
+            });
+        if let Some(i) = dismissed { self.toasts.remove(i); }
+        self.repaint.request_after(Duration::from_secs(1));
+    }
+
+    pub(crate) fn paint_particles(&self, ctx: &egui::Context) {
+        if self.particles.is_empty() { return; }
+        let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("confetti")));
+        for p in &self.particles {
+            let alpha = (p.life / (CONFETTI_LIFETIME * 0.5)).min(1.0);
+            let rect = egui::Rect::from_center_size(self.confetti_origin + p.pos, egui::vec2(p.size, p.size * 0.6));
+            painter.rect_filled(rect, 1.0, p.color.gamma_multiply(alpha));
+        }
+    }
+
+    pub(crate) fn paint_countdown(&self, ctx: &egui::Context) {
+        let Some(started_at) = self.countdown_started_at else { return; };
+        let elapsed = started_at.elapsed().as_secs_f32();
+        let phase = elapsed.fract();
+        let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("countdown")));
+        let colors = palette(&ctx.style().visuals);
+        let accent = accent_color(&self.settings.appearance, &ctx.style().visuals);
+        let size = 140.0 - 40.0 * phase;
+        painter.text(
+            self.confetti_origin,
+            egui::Align2::CENTER_CENTER,
+            format!("{}…", self.countdown_shown),
+            egui::FontId::proportional(size),
+            accent.unwrap_or(colors.heading).gamma_multiply(1.0 - 0.6 * phase),
+        );
+    }
+
+    pub(crate) fn roulette_ui(&mut self, ctx: &egui::Context) {
+        if self.theme_dirty {
+            apply_theme(ctx, self.settings.appearance.theme);
+            self.theme_dirty = false;
+        }
+        // A system theme can flip dark mode without theme_dirty, so compare with what the rows were built for.
+        let details_for = (self.settings.language, ctx.style().visuals.dark_mode);
+        if details_for != self.pool_details_for {
+            self.pool_details_for = details_for;
+            self.on_pool_changed();
+        }
+        if self.window_level_dirty {
+            apply_window_level(ctx, self.settings.appearance.always_on_top);
+            self.window_level_dirty = false;
+        }
+        let zoom = ctx.zoom_factor();
+        if self.ui_scale_dirty {
+            self.apply_ui_scale(ctx);
+            self.ui_scale_dirty = false;
+        } else if (zoom - self.applied_ui_scale).abs() > f32::EPSILON {
+            self.settings.appearance.ui_scale = zoom.clamp(UI_SCALE_MIN, UI_SCALE_MAX);
+            self.applied_ui_scale = zoom;
+            save_settings(&self.settings);
+        }
+        let lang = self.settings.language;
+        let colors = palette(&ctx.style().visuals);
+        let accent = accent_color(&self.settings.appearance, &ctx.style().visuals);
+
+        if !self.presentation {
+            self.status_bar_ui(ctx);
+            egui::TopBottomPanel::bottom("shortcut_hints").show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.label(egui::RichText::new(lang.pick(
+                        "Пробіл — крутити · R — оновити · P — пауза · Esc — пропустити · Ctrl+C — копіювати переможця · F11 — презентація",
+                        "Space — spin · R — refresh · P — pause · Esc — skip · Ctrl+C — copy winner · F11 — presentation",
+                    )).small().weak());
+                });
+            });
+        }
+        let text_scale = if self.presentation { PRESENTATION_TEXT_SCALE } else { 1.0 };
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            let metrics = LayoutMetrics::for_height(ui.available_height());
+            if metrics.compact { ui.spacing_mut().item_spacing.y = 6.0; }
+            if self.presentation {
+                ui.vertical_centered(|ui| ui.label(egui::RichText::new(lang.pick("F11 — вийти з презентації", "F11 to exit")).small().weak()));
+            }
+            ui.vertical_centered(|ui| {
+                let heading_size = if metrics.compact { 22.0 } else { 28.0 } * text_scale;
+                ui.heading(egui::RichText::new("🎰 SQUAD EU ROULETTE").size(heading_size).strong().color(accent.unwrap_or(colors.heading)));
+            });
+            if !self.presentation {
+                self.pin_button_ui(ui);
+                ui.vertical_centered(|ui| {
+                    let previous = self.view;
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.view, View::Roulette, lang.pick("🎰 Рулетка", "🎰 Roulette"));
+                        ui.selectable_value(&mut self.view, View::Table, lang.pick("📋 Сервери", "📋 Servers"));
+                        ui.selectable_value(&mut self.view, View::History, lang.pick("🕘 Історія", "🕘 History"));
+                        ui.selectable_value(&mut self.view, View::Settings, lang.pick("⚙ Налаштування", "⚙ Settings"));
+                    });
+                    if self.view != previous { self.switch_view(self.view); }
+                });
+            }
+            ui.add_space(metrics.small_gap);
+
+            if let Some(warning) = &self.config_warning {
+                let mut dismissed = false;
+                ui.horizontal(|ui| {
+                    ui.colored_label(colors.warning, format!("⚠ {}", warning));
+                    if icon_button(ui, true, "✖", lang.pick("Закрити", "Dismiss")).clicked() { dismissed = true; }
+                });
+                if dismissed { self.config_warning = None; }
+            }
+
+            match self.view {
+                View::Roulette => {
+                    if !self.presentation { self.filter_group_ui(ui, ctx); }
+                    self.show_roulette(ui, ctx, metrics);
+                }
+                View::Table => {
+                    self.filter_group_ui(ui, ctx);
+                    ui.add_space(metrics.small_gap);
+                    self.server_table_ui(ui, ctx);
+                }
+                View::History => self.show_history(ui, ctx),
+                View::Settings => self.show_settings(ui, ctx),
+            }
+        });
+
+        self.reset_dialog_ui(ctx);
+        self.onboarding_ui(ctx);
+        self.toasts_ui(ctx);
+        self.log_panel_ui(ctx);
+        self.perf_overlay_ui(ctx);
+        self.popout_ui(ctx);
+    }
+
+    fn show_roulette(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, metrics: LayoutMetrics) {
+        let lang = self.settings.language;
+        let colors = palette(ui.visuals());
+        let accent = accent_color(&self.settings.appearance, ui.visuals());
+        let text_scale = if self.presentation { PRESENTATION_TEXT_SCALE } else { 1.0 };
+        ui.add_space(metrics.section_gap);
+
+        let btn_text = match self.roulette_state {
+            RouletteState::Ready => lang.pick("🎰 КРУТИТИ!", "🎰 SPIN!"),
+            RouletteState::Loading => "⏳ ...",
+            RouletteState::Countdown => "⏱ ...",
+            RouletteState::Spinning => "🌀 ...",
+            RouletteState::Finished => lang.pick("🎰 ЩЕ РАЗ!", "🎰 AGAIN!"),
+        };
+        let blocker = self.spin_blocker();
+        let can_spin = blocker.is_none();
+
+        ui.horizontal(|ui| {
+            let spin_size = egui::vec2(250.0, metrics.button_height);
+            let quick_size = egui::vec2(160.0, metrics.button_height);
+            ui.add_space(((ui.available_width() - spin_size.x - quick_size.x - ui.spacing().item_spacing.x) / 2.0).max(0.0));
+            let mut spin_label = egui::RichText::new(btn_text).size(24.0).strong();
+            if let Some(accent) = accent.filter(|_| can_spin) { spin_label = spin_label.color(contrasting_text(accent)); }
+            let mut spin_button = egui::Button::new(spin_label).min_size(spin_size);
+            if let Some(accent) = accent.filter(|_| can_spin) { spin_button = spin_button.fill(accent); }
+            if gated_button(ui, blocker, spin_button, lang).clicked() {
+                self.request_spin();
+            }
+            let muted = !self.settings.audio.sound_enabled;
+            let speaker = egui::Button::new(egui::RichText::new(if muted { "🔇" } else { "🔊" }).size(16.0)).min_size(egui::vec2(metrics.button_height, metrics.button_height));
+            if ui.add(speaker).on_hover_text(if muted { lang.pick("Увімкнути звук", "Unmute") } else { lang.pick("Вимкнути звук", "Mute") }).clicked() {
+                self.settings.audio.sound_enabled = muted;
+                save_settings(&self.settings);
+            }
+            if self.roulette_state == RouletteState::Spinning {
+                let skip_button = egui::Button::new(egui::RichText::new(lang.pick("⏭ Пропустити", "⏭ Skip")).size(16.0)).min_size(quick_size);
+                if ui.add(skip_button).on_hover_text(lang.pick("Докрутити швидше (Esc)", "Fast-forward to the result (Esc)")).clicked() { self.skip_spin(); }
+                let pause_button = egui::Button::new(egui::RichText::new(if self.paused { "▶" } else { "⏸" }).size(16.0)).min_size(egui::vec2(metrics.button_height, metrics.button_height));
+                if ui.add(pause_button).on_hover_text(lang.pick("Пауза (P)", "Pause (P)")).clicked() { self.toggle_pause(); }
+            } else {
+                let quick_button = egui::Button::new(egui::RichText::new(lang.pick("⚡ Швидкий вибір", "⚡ Quick pick")).size(16.0)).min_size(quick_size);
+                if gated_button(ui, blocker, quick_button, lang).clicked() {
+                    self.quick_pick();
+                }
+            }
+        });
+        ui.vertical_centered(|ui| {
+            // Always reserve the caption line so the wheel doesn't jump when a spin starts.
+            let caption = blocker.map_or(" ", |b| b.reason(lang));
+            ui.label(egui::RichText::new(caption).small().color(colors.warning));
+        });
+
+        ui.add_space(metrics.section_gap);
+        
+        if !self.presentation { self.pool_preview_ui(ui); }
+        let scroll_height = metrics.scroll_height(ui.available_height(), self.row_height());
+        let mut empty_action = None;
+        
+        let canvas = egui::Frame::canvas(ui.style()).fill(colors.canvas_fill).stroke(egui::Stroke::new(1.0, colors.canvas_stroke)).inner_margin(0.0).show(ui, |ui| {
+            // Loading and empty states keep the list layout; the strip only takes over once there are cards to show.
+            let has_cards = self.roulette_state != RouletteState::Loading && !self.roulette_servers.is_empty();
+            if self.crossfade.is_some() { return self.crossfade_ui(ui, accent, text_scale, scroll_height); }
+            match self.settings.appearance.wheel_mode {
+                WheelMode::Horizontal if has_cards => return self.card_strip_ui(ui, ctx, accent, text_scale),
+                WheelMode::Circle if has_cards => return self.wheel_disc_ui(ui, ctx, accent, text_scale, scroll_height),
+                _ => {}
+            }
+            let row_height = self.row_height();
+            let center_y = scroll_height / 2.0 - row_height / 2.0;
+
+            let can_browse = matches!(self.roulette_state, RouletteState::Ready | RouletteState::Finished) && !self.roulette_servers.is_empty();
+            if !can_browse { self.browsing = false; }
+            let wheel_rect = egui::Rect::from_min_size(ui.cursor().min, egui::vec2(ui.available_width(), scroll_height));
+            let wheel_scrolled = ui.rect_contains_pointer(wheel_rect) && ui.input(|i| i.raw_scroll_delta.y != 0.0);
+            let mut area = egui::ScrollArea::vertical().max_height(scroll_height).enable_scrolling(self.browsing);
+            if can_browse && !self.browsing && wheel_scrolled {
+                // Browsing shows a single copy of the list, so carry the current view over modulo one repetition.
+                self.browsing = true;
+                let list_height = self.roulette_servers.len() as f32 * row_height;
+                area = area.vertical_scroll_offset((self.current_scroll - center_y).rem_euclid(list_height));
+            } else if !self.browsing {
+                // Outside browsing the content is exactly one view tall; the rows themselves follow current_scroll.
+                area = area.vertical_scroll_offset(0.0);
+            }
+
+            area.show_viewport(ui, |ui, viewport| {
+                ui.set_min_width(ui.available_width());
+
+                if self.roulette_state == RouletteState::Loading {
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), scroll_height), egui::Sense::hover());
+                    let shimmer = !self.effects_reduced();
+                    paint_loading_rows(ui, rect, row_height, &self.fetched_servers, colors, shimmer);
+                    if shimmer { self.repaint.request_now(); }
+                } else if self.roulette_servers.is_empty() {
+                    let summary = self.fetch_summary.filter(|s| !self.needs_update && !s.failed);
+                    match summary {
+                        Some(summary) => {
+                            ui.allocate_ui(egui::vec2(ui.available_width(), scroll_height), |ui| {
+                                // Set here rather than for the whole wheel: touching the style copies it, and the rows never needed it.
+                                ui.spacing_mut().item_spacing.y = 0.0;
+                                ui.set_min_height(scroll_height);
+                                ui.vertical_centered(|ui| {
+                                    ui.add_space(40.0);
+                                    ui.label(egui::RichText::new(lang.pick("Жоден сервер не підійшов", "No servers matched")).size(20.0).strong());
+                                    ui.label(format!("{} {} · {} {} · {} {}",
+                                        summary.total_seen, lang.pick("онлайн", "online"),
+                                        summary.excluded_region, lang.pick("відсіяно за країною", "excluded by country"),
+                                        summary.excluded_players, lang.pick("за кількістю гравців", "by player range")));
+                                    ui.add_space(10.0);
+                                    ui.horizontal(|ui| {
+                                        if summary.excluded_players > 0 && ui.button(lang.pick("↔ Розширити діапазон на 20", "↔ Widen player range by 20")).clicked() {
+                                            empty_action = Some(EmptyAction::WidenPlayers);
+                                        }
+                                        if self.settings.filters.region != Region::All && ui.button(lang.pick("🌍 Усі країни", "🌍 Include all countries")).clicked() {
+                                            empty_action = Some(EmptyAction::AllCountries);
+                                        }
+                                    });
+                                });
+                            });
+                        }
+                        None => {
+                            ui.allocate_space(egui::vec2(ui.available_width(), scroll_height));
+                            let text = if self.fetch_summary.is_some_and(|s| s.failed) {
+                                lang.pick("Не вдалося завантажити сервери. Спробуй ще раз.", "Failed to load servers. Try again.")
+                            } else {
+                                lang.pick("Список порожній. Онови сервери!", "The list is empty. Refresh the servers!")
+                            };
+                            ui.centered_and_justified(|ui| { ui.label(text); });
+                        }
+                    }
+                } else {
+                    let server_count = self.roulette_servers.len();
+                    let spinning = self.roulette_state == RouletteState::Spinning;
+
+                    let speed = if spinning { self.scroll_speed } else { 0.0 };
+                    let detail = RowDetail::for_speed(speed);
+                    let fade = if self.effects_reduced() { 0.0 } else { (speed / MOTION_FADE_SPEED).min(1.0) * 0.85 };
+                    let half_view = scroll_height / 2.0;
+                    let focus = !self.effects_reduced() && !self.browsing;
+
+                    let mut actions: Vec<(usize, RowAction)> = Vec::new();
+                    // Browsing scrolls through one copy of the list; otherwise the view is a window onto the endless
+                    // wheel and only the handful of rows inside it are laid out.
+                    let (rows, content) = if self.browsing {
+                        let (_, content) = ui.allocate_space(egui::vec2(ui.available_width(), server_count as f32 * row_height));
+                        let first = (viewport.min.y / row_height).floor().max(0.0) as i64;
+                        let last = ((viewport.max.y / row_height).ceil() as i64).min(server_count as i64);
+                        (first..last, content)
+                    } else {
+                        let (_, content) = ui.allocate_space(egui::vec2(ui.available_width(), scroll_height));
+                        (self.rows_in_view(scroll_height, row_height), content)
+                    };
+                    let top = if self.browsing { content.top() } else { content.top() + center_y - self.current_scroll };
+                    for row in rows {
+                        let idx = server_at(row, server_count);
+                        let server = &self.roulette_servers[idx];
+                        // Every row takes exactly row_height, whatever its content, or the pointer drifts off the winner over a long list.
+                        let row_rect = egui::Rect::from_min_size(egui::pos2(content.left(), top + row as f32 * row_height), egui::vec2(content.width(), row_height));
+                        let offset = (row as f32 * row_height - self.current_scroll).abs();
+                        let (zoom, focus_opacity) = if focus { focus_emphasis(offset / row_height) } else { (1.0, 1.0) };
+                        // Only the content scales: the full width is reserved for the focused row, the height never changes.
+                        let content_rect = row_rect.shrink2(egui::vec2(row_rect.width() * (1.0 - zoom / FOCUS_MAX_SCALE) / 2.0, 0.0));
+                        let ui = &mut ui.new_child(egui::UiBuilder::new().max_rect(if focus { content_rect } else { row_rect }));
+                        ui.set_clip_rect(row_rect.intersect(ui.clip_rect()));
+                        ui.multiply_opacity(focus_opacity * (1.0 - fade * (offset / half_view).min(1.0).powi(2)));
+                        let text_scale = text_scale * zoom * self.settings.appearance.row_size.text_scale();
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(4.0); 
+                            let details = self.pool_details.get(idx);
+                            // With a tiny pool every repetition looks the same, so alternate the shade per pass to keep the motion visible.
+                            let shaded = server_count <= SMALL_POOL_MAX && row.div_euclid(server_count as i64) % 2 != 0;
+                            let look = RowLook { detail, height: row_height, text_scale, borders: self.settings.appearance.row_borders, shaded };
+                            let row = wheel_row_ui(ui, server, details, colors, look);
+                            if detail != RowDetail::Bars { self.wheel_item_interactions(row, idx, &mut actions); }
+                        });
+                    }
+                    self.apply_row_actions(ctx, actions);
+                }
+            });
+
+            let rect = ui.min_rect();
+            let line_y = rect.top() + scroll_height / 2.0;
+            let painter = ui.painter().with_clip_rect(rect);
+            self.confetti_origin = egui::pos2(rect.center().x, line_y);
+            if self.highlight > 0.0 && self.roulette_state == RouletteState::Finished && !self.roulette_servers.is_empty() && !self.browsing {
+                let row_center_y = line_y + self.pointer_row() as f32 * row_height - self.current_scroll;
+                let row_rect = egui::Rect::from_center_size(egui::pos2(rect.center().x, row_center_y), egui::vec2(rect.width() - 10.0, row_height - 8.0));
+                paint_winner_glow(&painter, row_rect, self.highlight, accent.unwrap_or(colors.heading));
+            }
+            paint_edge_fade(&painter, rect, colors.canvas_fill);
+            if self.roulette_state != RouletteState::Loading {
+                painter.line_segment([egui::pos2(rect.left(), line_y), egui::pos2(rect.right(), line_y)], egui::Stroke::new(3.0, accent.unwrap_or(colors.danger)));
+            }
+            painter.text(egui::pos2(rect.right() - 10.0, line_y), egui::Align2::RIGHT_CENTER, "◄", egui::FontId::proportional(30.0), colors.danger);
+        }).response.rect;
+        if self.paused { self.pause_overlay_ui(ui, canvas); }
+
+        if matches!(self.roulette_state, RouletteState::Spinning | RouletteState::Finished) && !self.browsing {
+            if let Some(name) = self.passing_name() {
+                ui.vertical_centered(|ui| {
+                    ui.add(egui::Label::new(egui::RichText::new(name).size(26.0 * text_scale).strong().color(accent.unwrap_or(colors.heading))).truncate());
+                });
+            }
+        }
+
+        if let Some(action) = empty_action {
+            let filters = &mut self.settings.filters;
+            match action {
+                EmptyAction::WidenPlayers => {
+                    let up = (100 - filters.max_players.min(100)).min(10);
+                    let down = filters.min_players.min(20 - up);
+                    filters.max_players += up;
+                    filters.min_players -= down;
+                }
+                EmptyAction::AllCountries => filters.region = Region::All,
+            }
+            save_settings(&self.settings);
+            if self.raw_covers_filters() { self.refilter(); } else { self.start_fetch(ctx.clone()); }
+        }
+
+        if self.roulette_state == RouletteState::Finished {
+            if let Some(winner) = &self.selected_server {
+                ui.add_space(metrics.section_gap);
+                let details = self.winner_details.get(winner, lang, ui.visuals().dark_mode);
+                let card = ui.scope(|ui| if metrics.compact {
+                    winner_strip_ui(ui, ctx, winner, &self.settings, self.winner_reveal, self.manual_pick)
+                } else {
+                    winner_card_ui(ui, ctx, winner, details, &self.settings, self.winner_reveal, self.manual_pick)
+                });
+                if self.replay.is_some() { paint_replay_watermark(ui.painter(), card.response.rect, colors); }
+                if let Some(url) = card.inner { self.open_in_browser(url); }
+                let mut exit_replay = false;
+                ui.vertical_centered(|ui| {
+                    if self.replay.is_some() {
+                        exit_replay = ui.button(lang.pick("⏹ Вийти з повтору", "⏹ Exit replay")).clicked();
+                    } else if ui.add_enabled(!self.show_popout, egui::Button::new(lang.pick("🗗 Окреме вікно", "🗗 Pop out"))).clicked() {
+                        self.show_popout = true;
+                    }
+                });
+                if exit_replay { self.exit_replay(); }
+            }
+        }
+
+    }
+
+    fn wheel_item_interactions(&self, response: egui::Response, idx: usize, actions: &mut Vec<(usize, RowAction)>) {
+        let Some(server) = self.roulette_servers.get(idx) else { return; };
+        let lang = self.settings.language;
+        let spinning = self.roulette_state == RouletteState::Spinning;
+        let idle = matches!(self.roulette_state, RouletteState::Ready | RouletteState::Finished);
+        let mut response = response.interact(egui::Sense::click());
+        if !spinning {
+            if let Some(details) = self.pool_details.get(idx) {
+                response = response.on_hover_ui(|ui| {
+                    ui.label(egui::RichText::new(&server.name).strong());
+                    server_details_grid(ui, "row_details", server, details, lang);
+                    if idle { ui.label(egui::RichText::new(lang.pick("Подвійний клік — обрати вручну", "Double-click to pick manually")).weak()); }
+                });
+            }
+        }
+        if idle && response.double_clicked() { actions.push((idx, RowAction::PickAsWinner)); }
+        let favorite = self.lists.is_favorite(&server.id);
+        response.context_menu(|ui| row_context_menu(ui, lang, favorite, !spinning, idx, actions));
+    }
+
+    fn pause_overlay_ui(&mut self, ui: &mut egui::Ui, rect: egui::Rect) {
+        let lang = self.settings.language;
+        ui.painter().rect_filled(rect, 0.0, egui::Color32::from_black_alpha(160));
+        ui.allocate_new_ui(egui::UiBuilder::new().max_rect(rect), |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space((rect.height() / 2.0 - 60.0).max(0.0));
+                ui.label(egui::RichText::new(lang.pick("⏸ Пауза", "⏸ Paused")).size(26.0).strong().color(egui::Color32::WHITE));
+                ui.add_space(8.0);
+                if ui.button(egui::RichText::new(lang.pick("▶ Продовжити", "▶ Resume")).size(16.0)).clicked() { self.toggle_pause(); }
+                if ui.button(egui::RichText::new(lang.pick("✖ Скасувати спін", "✖ Cancel spin")).size(16.0))
+                    .on_hover_text(lang.pick("Переможця не буде, в історію нічого не запишеться", "No winner is announced and nothing goes into the history"))
+                    .clicked() { self.cancel_spin(); }
+            });
+        });
+    }
+
+    fn crossfade_ui(&mut self, ui: &mut egui::Ui, accent: Option<egui::Color32>, text_scale: f32, height: f32) {
+        let Some(crossfade) = &self.crossfade else { return; };
+        let colors = palette(ui.visuals());
+        let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::hover());
+        let painter = ui.painter().with_clip_rect(rect);
+        let blend = crossfade.blend();
+        let shown = [(Some(crossfade.current()), 1.0 - blend), (crossfade.next(), blend)];
+        for (idx, alpha) in shown {
+            let Some(server) = idx.and_then(|i| self.roulette_servers.get(i)) else { continue; };
+            if alpha <= 0.0 { continue; }
+            let details = self.pool_details.get(idx.unwrap_or_default());
+            let title = details.map_or(&server.name, |d| &d.title);
+            painter.text(rect.center() - egui::vec2(0.0, 14.0 * text_scale), egui::Align2::CENTER_CENTER, title,
+                egui::FontId::proportional(30.0 * text_scale), accent.unwrap_or(colors.server_name).gamma_multiply(alpha));
+            painter.text(rect.center() + egui::vec2(0.0, 22.0 * text_scale), egui::Align2::CENTER_CENTER, details.map_or_else(|| format!("🗺️ {}   👥 {}", server.map, server.players_text()), |d| d.summary.clone()),
+                egui::FontId::proportional(16.0 * text_scale), colors.players.gamma_multiply(alpha));
+        }
+        self.confetti_origin = rect.center();
+    }
+
+    fn card_strip_ui(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, accent: Option<egui::Color32>, text_scale: f32) {
+        let colors = palette(ui.visuals());
+        let width = ui.available_width();
+        let center_x = width / 2.0 - CARD_WIDTH / 2.0;
+        let server_count = self.roulette_servers.len();
+        let spinning = self.roulette_state == RouletteState::Spinning;
+        let speed = if spinning { self.scroll_speed } else { 0.0 };
+        let detail = RowDetail::for_speed(speed);
+        let fade = if self.effects_reduced() { 0.0 } else { (speed / MOTION_FADE_SPEED).min(1.0) * 0.85 };
+        let half_view = width / 2.0;
+        let focus = !self.effects_reduced();
+        self.browsing = false;
+
+        let mut actions: Vec<(usize, RowAction)> = Vec::new();
+        let (strip, _) = ui.allocate_exact_size(egui::vec2(width, CARD_HEIGHT), egui::Sense::hover());
+        let left = strip.left() + center_x - self.current_scroll;
+        for slot in self.rows_in_view(width, CARD_WIDTH) {
+            let idx = server_at(slot, server_count);
+            let server = &self.roulette_servers[idx];
+            let card_rect = egui::Rect::from_min_size(egui::pos2(left + slot as f32 * CARD_WIDTH, strip.top()), egui::vec2(CARD_WIDTH, CARD_HEIGHT));
+            let ui = &mut ui.new_child(egui::UiBuilder::new().max_rect(card_rect));
+            ui.set_clip_rect(card_rect.intersect(strip).intersect(ui.clip_rect()));
+            let offset = (slot as f32 * CARD_WIDTH - self.current_scroll).abs();
+            let (zoom, focus_opacity) = if focus { focus_emphasis(offset / CARD_WIDTH) } else { (1.0, 1.0) };
+            ui.multiply_opacity(focus_opacity * (1.0 - fade * (offset / half_view).min(1.0).powi(2)));
+            let text_scale = text_scale * zoom;
+            let shaded = server_count <= SMALL_POOL_MAX && slot.div_euclid(server_count as i64) % 2 != 0;
+            let look = RowLook { detail, height: CARD_HEIGHT, text_scale, borders: self.settings.appearance.row_borders, shaded };
+            let card = wheel_card_ui(ui, server, self.pool_details.get(idx), colors, look);
+            if detail != RowDetail::Bars { self.wheel_item_interactions(card, idx, &mut actions); }
+        }
+        self.apply_row_actions(ctx, actions);
+
+        let rect = ui.min_rect();
+        let line_x = rect.left() + width / 2.0;
+        let painter = ui.painter().with_clip_rect(rect);
+        self.confetti_origin = egui::pos2(line_x, rect.center().y);
+        if self.highlight > 0.0 && self.roulette_state == RouletteState::Finished {
+            let centered_idx = (self.current_scroll / CARD_WIDTH).round();
+            let card_center_x = line_x + centered_idx * CARD_WIDTH - self.current_scroll;
+            let card_rect = egui::Rect::from_center_size(egui::pos2(card_center_x, rect.center().y), egui::vec2(CARD_WIDTH - 8.0, CARD_HEIGHT - 8.0));
+            paint_winner_glow(&painter, card_rect, self.highlight, accent.unwrap_or(colors.heading));
+        }
+        painter.line_segment([egui::pos2(line_x, rect.top()), egui::pos2(line_x, rect.bottom())], egui::Stroke::new(3.0, accent.unwrap_or(colors.danger)));
+        painter.text(egui::pos2(line_x, rect.top() + 2.0), egui::Align2::CENTER_TOP, "▼", egui::FontId::proportional(26.0), colors.danger);
+    }
+
+    fn wheel_disc_ui(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, accent: Option<egui::Color32>, text_scale: f32, height: f32) {
+        use std::f32::consts::{FRAC_PI_2, TAU};
+        let colors = palette(ui.visuals());
+        let lang = self.settings.language;
+        let count = self.roulette_servers.len();
+        let sector = TAU / count as f32;
+        let labeled = count <= WHEEL_LABEL_MAX;
+        self.browsing = false;
+
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::click());
+        let center = rect.center();
+        let radius = (rect.width().min(height) / 2.0 - 12.0).max(20.0);
+        let rotation = self.current_scroll / self.item_pitch() * sector;
+        // Sector i is centred under the top pointer exactly when current_scroll = i * pitch, like a row on the centre line.
+        let angle_of = |i: usize| -FRAC_PI_2 + i as f32 * sector - rotation;
+        let direction = |angle: f32| egui::vec2(angle.cos(), angle.sin());
+        let sector_color = |i: usize| {
+            if labeled { return egui::ecolor::Hsva::new(i as f32 / count as f32, 0.55, 0.75, 1.0).into(); }
+            let population = self.pool_details.get(i).map_or(Population::Low, |d| d.population);
+            let color = colors.population_color(population, ui.visuals());
+            if i % 2 == 1 { color.gamma_multiply(0.7) } else { color }
+        };
+        let painter = ui.painter().with_clip_rect(rect);
+
+        let segments = (WHEEL_SEGMENTS / count).max(2);
+        let mut mesh = egui::Mesh::default();
+        for i in 0..count {
+            let color = sector_color(i);
+            let start = angle_of(i) - sector / 2.0;
+            let base = mesh.vertices.len() as u32;
+            mesh.colored_vertex(center, color);
+            for step in 0..=segments {
+                mesh.colored_vertex(center + radius * direction(start + sector * step as f32 / segments as f32), color);
+            }
+            for step in 0..segments as u32 { mesh.add_triangle(base, base + 1 + step, base + 2 + step); }
+        }
+        painter.add(mesh);
+        painter.circle_stroke(center, radius, egui::Stroke::new(2.0, colors.canvas_stroke));
+
+        if labeled {
+            let size = (radius * sector * 0.45).clamp(9.0, 16.0) * text_scale;
+            for (i, server) in self.roulette_servers.iter().enumerate() {
+                let angle = angle_of(i);
+                let text_color = contrasting_text(sector_color(i));
+                let mut job = egui::text::LayoutJob::simple_singleline(server.name.clone(), egui::FontId::proportional(size), text_color);
+                job.wrap = egui::text::TextWrapping::truncate_at_width(radius * 0.7);
+                let galley = ui.fonts(|f| f.layout_job(job));
+                // Names read outward along the radius, centred across it.
+                let across = egui::vec2(-angle.sin(), angle.cos());
+                let pos = center + radius * 0.22 * direction(angle) - across * galley.size().y / 2.0;
+                painter.add(egui::epaint::TextShape::new(pos, galley, text_color).with_angle(angle));
+            }
+        } else {
+            let mut y = rect.top() + 8.0;
+            for population in Population::ALL {
+                let swatch = egui::Rect::from_min_size(egui::pos2(rect.left() + 10.0, y), egui::vec2(12.0, 12.0));
+                painter.rect_filled(swatch, 2.0, colors.population_color(population, ui.visuals()));
+                painter.text(swatch.right_center() + egui::vec2(6.0, 0.0), egui::Align2::LEFT_CENTER, population.label(lang), egui::FontId::proportional(12.0), ui.visuals().text_color());
+                y += 18.0;
+            }
+        }
+        painter.circle_filled(center, radius * 0.12, colors.canvas_fill);
+
+        if self.highlight > 0.0 && self.roulette_state == RouletteState::Finished {
+            let start = -FRAC_PI_2 - sector / 2.0;
+            let mut outline = vec![center];
+            outline.extend((0..=segments).map(|step| center + radius * direction(start + sector * step as f32 / segments as f32)));
+            painter.add(egui::Shape::closed_line(outline, egui::Stroke::new(3.0, accent.unwrap_or(colors.heading).gamma_multiply(self.highlight.sqrt()))));
+        }
+        let tip = center - egui::vec2(0.0, radius - 8.0);
+        painter.add(egui::Shape::convex_polygon(vec![tip, tip + egui::vec2(12.0, -24.0), tip + egui::vec2(-12.0, -24.0)], accent.unwrap_or(colors.danger), egui::Stroke::new(1.0, colors.canvas_stroke)));
+        self.confetti_origin = egui::pos2(center.x, center.y - radius);
+
+        let hovered = response.hover_pos().filter(|pos| pos.distance(center) <= radius).map(|pos| {
+            let offset = pos - center;
+            ((offset.y.atan2(offset.x) + FRAC_PI_2 + rotation) / sector).round().rem_euclid(count as f32) as usize % count
+        });
+        let mut actions: Vec<(usize, RowAction)> = Vec::new();
+        if let Some(idx) = hovered { self.wheel_item_interactions(response, idx, &mut actions); }
+        self.apply_row_actions(ctx, actions);
+    }
+
+    fn filter_group_ui(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let lang = self.settings.language;
+        let colors = palette(ui.visuals());
+        ui.group(|ui| {
+            ui.style_mut().spacing.slider_width = 250.0; 
+            ui.style_mut().spacing.interact_size.y = MIN_HIT_SIZE; 
+            
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(lang.pick("Гравці:", "Players:")).size(18.0));
+                let min_changed = slider_with_value(ui, &mut self.settings.filters.min_players, 0..=100, lang.pick("мін", "min"));
+                let max_changed = slider_with_value(ui, &mut self.settings.filters.max_players, 0..=100, lang.pick("макс", "max"));
+                if min_changed.changed() || max_changed.changed() {
+                    self.settings.filters.enforce_player_bounds(min_changed.changed());
+                    self.on_filters_changed();
+                }
+                let committed = |r: &egui::Response| r.drag_stopped() || (r.changed() && !r.dragged());
+                if committed(&min_changed) || committed(&max_changed) { save_settings(&self.settings); }
+            });
+            
+            ui.add_space(5.0);
+            ui.horizontal(|ui| {
+                let refresh_button = egui::Button::new(lang.pick("🔄 Оновити", "🔄 Refresh"));
+                if gated_button(ui, self.refresh_blocker(), refresh_button, lang).clicked() { self.start_fetch(ctx.clone()); }
+                if self.roulette_state == RouletteState::Loading {
+                    ui.spinner();
+                    match self.fetch_progress {
+                        Some(progress) if progress.pages_done > 0 => {
+                            ui.add(egui::ProgressBar::new(progress.fraction()).desired_width(150.0).show_percentage());
+                            ui.label(format!("{} {}", lang.pick("Знайдено:", "Matched:"), self.fetched_servers.len()));
+                        }
+                        _ => { ui.label(lang.pick("Завантаження…", "Loading…")); }
+                    }
+                }
+                else if self.needs_update { ui.colored_label(colors.warning, lang.pick("Дані застаріли!", "Data is stale!")); } 
+                else { ui.colored_label(colors.ok, format!("{} {}", lang.pick("Серверів:", "Servers:"), self.roulette_servers.len())); }
+            });
+            if !self.pool_summary.is_empty() && self.roulette_state != RouletteState::Loading {
+                ui.label(egui::RichText::new(&self.pool_summary).weak());
+            }
+        });
+        self.filter_chips_ui(ui);
+    }
+
+    fn filter_chips_ui(&mut self, ui: &mut egui::Ui) {
+        let chips = active_filter_chips(&self.settings);
+        if chips.is_empty() { return; }
+        let lang = self.settings.language;
+        let can_edit = self.can_reset();
+        let mut cleared = None;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(egui::RichText::new(lang.pick("Активні фільтри:", "Active filters:")).weak());
+            for chip in &chips {
+                egui::Frame::group(ui.style()).rounding(12.0).inner_margin(egui::Margin::symmetric(8.0, 0.0)).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(&chip.label);
+                        if icon_button(ui, can_edit, "✖", lang.pick("Скинути цей фільтр", "Reset this filter")).clicked() { cleared = Some(chip.kind); }
+                    });
+                });
+            }
+        });
+        if let Some(kind) = cleared {
+            self.settings.filters.reset_chip(kind);
+            self.on_filters_changed();
+            save_settings(&self.settings);
+        }
+    }
+
+    fn popout_ui(&mut self, ctx: &egui::Context) {
+        if !self.show_popout { return; }
+        let Some(winner) = self.popout_winner.clone() else { self.show_popout = false; return; };
+        let lang = self.settings.language;
+        let mut builder = egui::ViewportBuilder::default()
+            .with_title(lang.pick("Переможець", "Winner"))
+            .with_inner_size([400.0, 200.0])
+            .with_always_on_top();
+        if let Some(pos) = self.settings.appearance.popout_pos { builder = builder.with_position(pos); }
+
+        let mut open_url = None;
+        ctx.show_viewport_immediate(egui::ViewportId::from_hash_of("winner_popout"), builder, |ctx, class| {
+            let mut body = |ui: &mut egui::Ui| {
+                let details = self.popout_details.get(&winner, lang, ui.visuals().dark_mode);
+                egui::ScrollArea::vertical().show(ui, |ui| open_url = winner_card_ui(ui, ctx, &winner, details, &self.settings, 1.0, self.manual_pick));
+            };
+            if class == egui::ViewportClass::Embedded {
+                let mut open = true;
+                egui::Window::new(lang.pick("Переможець", "Winner")).open(&mut open).default_size([400.0, 200.0]).show(ctx, |ui| body(ui));
+                if !open { self.show_popout = false; }
+                return;
+            }
+            egui::CentralPanel::default().show(ctx, |ui| body(ui));
+            if let Some(rect) = ctx.input(|i| i.viewport().outer_rect) {
+                let pos = [rect.min.x, rect.min.y];
+                if self.settings.appearance.popout_pos != Some(pos) {
+                    self.settings.appearance.popout_pos = Some(pos);
+                    self.settings_dirty = true;
+                }
+            }
+            if ctx.input(|i| i.viewport().close_requested()) { self.show_popout = false; }
+        });
+        if let Some(url) = open_url { self.open_in_browser(url); }
+        if !self.show_popout && self.settings_dirty {
+            save_settings(&self.settings);
+            self.settings_dirty = false;
+        }
+    }
+
+    fn onboarding_ui(&mut self, ctx: &egui::Context) {
+        if !self.show_onboarding { return; }
+        let lang = self.settings.language;
+        let mut finished = false;
+        egui::Window::new(lang.pick("👋 Ласкаво просимо", "👋 Welcome"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                egui::Grid::new("onboarding").num_columns(2).spacing([20.0, 12.0]).show(ui, |ui| {
+                    ui.label(lang.pick("Мова:", "Language:"));
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.settings.language, Language::Uk, "Українська");
+                        ui.selectable_value(&mut self.settings.language, Language::En, "English");
+                    });
+                    ui.end_row();
+
+                    ui.label(lang.pick("Регіон:", "Region:"));
+                    let filters = &mut self.settings.filters;
+                    egui::ComboBox::from_id_salt("onboarding_region")
+                        .selected_text(filters.region.label(lang))
+                        .show_ui(ui, |ui| {
+                            for region in [Region::Eu, Region::Na, Region::All] {
+                                ui.selectable_value(&mut filters.region, region, region.label(lang));
+                            }
+                        });
+                    ui.end_row();
+
+                    ui.label(lang.pick("Гравці:", "Players:"));
+                    ui.horizontal(|ui| {
+                        ui.add(egui::DragValue::new(&mut filters.min_players).range(0..=100));
+                        ui.label("–");
+                        ui.add(egui::DragValue::new(&mut filters.max_players).range(0..=100));
+                    });
+                    ui.end_row();
+
+                    ui.label(lang.pick("Анімація:", "Animation:"));
+                    ui.checkbox(&mut self.settings.animation.reduced_motion, lang.pick("🐢 Без руху", "🐢 Reduced motion")).on_hover_text(lang.pick("Замість прокрутки імена плавно змінюють одне одного на місці; без конфеті та інших ефектів", "Instead of scrolling, names fade into one another in place; no confetti or other effects"));
+                    ui.end_row();
+                });
+                ui.add_space(10.0);
+                ui.vertical_centered(|ui| {
+                    let can_finish = self.roulette_state != RouletteState::Spinning;
+                    if ui.add_enabled(can_finish, egui::Button::new(egui::RichText::new(lang.pick("Почати", "Start")).size(18.0))).clicked() { finished = true; }
+                });
+            });
+        if finished {
+            if self.settings.filters.min_players > self.settings.filters.max_players {
+                std::mem::swap(&mut self.settings.filters.min_players, &mut self.settings.filters.max_players);
+            }
+            self.settings.onboarding_done = true;
+            self.show_onboarding = false;
+            save_settings(&self.settings);
+            self.rebuild_pool();
+            self.start_fetch(ctx.clone());
+        }
+    }
+
+    fn perf_overlay_ui(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| i.key_pressed(egui::Key::F9)) { self.show_perf_overlay = !self.show_perf_overlay; }
+        let now = ctx.input(|i| i.time);
+        self.frame_times.push_back(now);
+        while self.frame_times.len() > PERF_SAMPLE_FRAMES { self.frame_times.pop_front(); }
+        if !self.show_perf_overlay { return; }
+
+        let span = self.frame_times.back().zip(self.frame_times.front()).map_or(0.0, |(last, first)| last - first);
+        let fps = if span > 0.0 { (self.frame_times.len() - 1) as f64 / span } else { 0.0 };
+        let causes = ctx.repaint_causes();
+        egui::Area::new(egui::Id::new("perf_overlay"))
+            .anchor(egui::Align2::LEFT_TOP, egui::vec2(8.0, 8.0))
+            .order(egui::Order::Tooltip)
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(egui::RichText::new(format!("{:.1} fps · {} frames / {:.1} s", fps, self.frame_times.len(), span)).monospace());
+                    if causes.is_empty() {
+                        ui.label(egui::RichText::new("repaint: input only").monospace().weak());
+                    }
+                    for cause in causes.iter().take(5) {
+                        ui.label(egui::RichText::new(format!("repaint: {:?}", cause)).monospace().small());
+                    }
+                });
+            });
+    }
+
+    fn log_panel_ui(&mut self, ctx: &egui::Context) {
+        if ctx.input(|i| i.key_pressed(egui::Key::F12)) { self.show_log_panel = !self.show_log_panel; }
+        if !self.show_log_panel { return; }
+        let mut open = true;
+        let lang = self.settings.language;
+        egui::Window::new(lang.pick("🪲 Логи", "🪲 Logs"))
+            .open(&mut open)
+            .default_size(egui::vec2(600.0, 300.0))
+            .show(ctx, |ui| {
+                let lines: Vec<LogLine> = LOG_LINES.lock().map(|l| l.iter().filter(|line| line.level <= self.log_level_filter).cloned().collect()).unwrap_or_default();
+                ui.horizontal(|ui| {
+                    egui::ComboBox::from_id_salt("log_level")
+                        .selected_text(self.log_level_filter.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in [Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG] {
+                                ui.selectable_value(&mut self.log_level_filter, level, level.as_str());
+                            }
+                        });
+                    if ui.add_enabled(self.can_reset(), egui::Button::new(lang.pick("🧪 Тестовий пул", "🧪 Sample pool"))).clicked() {
+                        self.fetched_servers = sample_servers();
+                        // The sample ignores the filters; changing them afterwards asks for a real fetch.
+                        self.raw_servers.clear();
+                        self.fetch_summary = None;
+                        self.needs_update = false;
+                        self.selected_server = None;
+                        self.roulette_state = RouletteState::Ready;
+                        self.rebuild_pool();
+                    }
+                    if ui.button(lang.pick("📋 Копіювати логи", "📋 Copy logs")).clicked() {
+                        let text = lines.iter().map(|l| l.text.as_str()).collect::<Vec<_>>().join("\n");
+                        ctx.output_mut(|o| o.copied_text = text);
+                    }
+                });
+                ui.separator();
+                egui::ScrollArea::vertical().stick_to_bottom(true).auto_shrink([false, false]).show(ui, |ui| {
+                    let colors = palette(ui.visuals());
+                    for line in &lines {
+                        let color = match line.level {
+                            Level::ERROR => colors.danger,
+                            Level::WARN => colors.warning,
+                            _ => ui.visuals().text_color(),
+                        };
+                        ui.label(egui::RichText::new(&line.text).monospace().color(color));
+                    }
+                });
+            });
+        if !open { self.show_log_panel = false; }
+    }
+}
@@ -0,0 +1,306 @@
+use eframe::egui;
+
+use squad_roulette::animation::Easing;
+use squad_roulette::audio::{synthesize_click, AudioEngine, SoundTheme};
+use squad_roulette::model::{RouletteState, RowSize, WheelMode, SPIN_DURATION_MAX, SPIN_DURATION_MIN};
+use squad_roulette::storage::save_settings;
+
+use crate::app::{ResetScope, RouletteApp, UI_SCALE_MAX, UI_SCALE_MIN};
+use crate::ui::theme::palette;
+use crate::ui::widgets::{easing_preview, paint_waveform, sound_theme_label};
+
+impl RouletteApp {
+    fn sound_file_ui(&mut self, ui: &mut egui::Ui, label: &str, tick: bool) {
+        let lang = self.settings.language;
+        ui.horizontal(|ui| {
+            ui.label(label);
+            let audio = &mut self.settings.audio;
+            let path = if tick { &mut audio.tick_file } else { &mut audio.win_file };
+            let edit = ui.add(egui::TextEdit::singleline(path).hint_text(lang.pick("шлях до .wav / .ogg / .mp3", "path to a .wav / .ogg / .mp3")).desired_width(260.0));
+            let mut changed = edit.lost_focus();
+            if !path.is_empty() && ui.button("✖").on_hover_text(lang.pick("Стандартний звук", "Built-in sound")).clicked() {
+                path.clear();
+                changed = true;
+            }
+            if ui.button("▶").on_hover_text(lang.pick("Прослухати", "Preview")).clicked() {
+                if tick { self.play_click(); } else { self.play_fanfare(); }
+            }
+            if changed {
+                self.load_custom_sounds();
+                save_settings(&self.settings);
+            }
+        });
+    }
+
+    fn sound_lab_ui(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        let mut params = self.settings.audio.click();
+        let mut apply = false;
+        let mut changed = false;
+        ui.group(|ui| {
+            ui.label(egui::RichText::new(lang.pick("🧪 Звукова лабораторія", "🧪 Sound lab")).strong());
+            if !self.settings.audio.tick_file.trim().is_empty() {
+                ui.label(lang.pick("Зараз грає власний файл тіку; налаштування діють без нього.", "Your own tick file is playing; these settings apply without it."));
+            }
+            let mut slider = |response: egui::Response| {
+                changed |= response.changed();
+                apply |= response.drag_stopped() || (response.changed() && !response.dragged());
+            };
+            slider(ui.add(egui::Slider::new(&mut params.duration_ms, 5..=80).suffix(lang.pick(" мс", " ms")).text(lang.pick("Тривалість", "Duration"))));
+            slider(ui.add(egui::Slider::new(&mut params.smoothing, 0.0..=0.98).text(lang.pick("Фільтр (вище — глухіше)", "Filter (higher is duller)"))));
+            slider(ui.add(egui::Slider::new(&mut params.decay_power, 0.5..=6.0).text(lang.pick("Степінь згасання", "Decay exponent"))));
+            slider(ui.add(egui::Slider::new(&mut params.gain, 0.05..=1.0).text(lang.pick("Гучність піку", "Peak gain"))));
+            if changed || self.lab_preview.is_empty() { self.lab_preview = synthesize_click(&params); }
+            paint_waveform(ui, &self.lab_preview);
+            ui.horizontal(|ui| {
+                if ui.button(lang.pick("▶ Прослухати", "▶ Play")).clicked() { self.play_click(); }
+                if self.settings.audio.click_tuning.is_some() && ui.button(lang.pick("Як у темі", "Theme default")).clicked() {
+                    self.settings.audio.click_tuning = None;
+                    self.lab_preview.clear();
+                    self.load_custom_sounds();
+                    save_settings(&self.settings);
+                }
+            });
+        });
+        if changed { self.settings.audio.click_tuning = Some(params); }
+        if apply {
+            self.load_custom_sounds();
+            save_settings(&self.settings);
+        }
+    }
+
+    pub(crate) fn show_settings(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        egui::ScrollArea::vertical().id_salt("settings").auto_shrink([false, false]).show(ui, |ui| {
+            self.settings_ui(ui, ctx);
+        });
+    }
+
+    fn settings_ui(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let lang = self.settings.language;
+        ui.horizontal_wrapped(|ui| {
+            if ui.checkbox(&mut self.settings.audio.sound_enabled, lang.pick("🔊 Звук", "🔊 Sound")).changed() { save_settings(&self.settings); }
+            let volume = ui.add_enabled(self.settings.audio.sound_enabled, egui::Slider::new(&mut self.settings.audio.volume, 0..=100).suffix("%"));
+            // Preview on release rather than on every step, or dragging would rattle off a click per percent.
+            let volume = volume.on_hover_text(lang.pick("Гучність", "Volume"));
+            if volume.drag_stopped() || (volume.changed() && !volume.dragged()) {
+                self.play_click();
+                save_settings(&self.settings);
+            }
+            let drumroll = ui.checkbox(&mut self.settings.audio.drumroll, lang.pick("🥁 Барабанний дріб", "🥁 Drumroll"));
+            if drumroll.on_hover_text(lang.pick("Тихий дріб, поки крутиться колесо", "A soft roll while the wheel turns")).changed() {
+                if !self.settings.audio.drumroll { self.audio.stop_loop(); }
+                save_settings(&self.settings);
+            }
+            let instant_fanfare = ui.checkbox(&mut self.settings.audio.instant_fanfare, lang.pick("🎺 Фанфари без анімації", "🎺 Fanfare without animation"));
+            if instant_fanfare.on_hover_text(lang.pick("Фанфари також для швидкого вибору та вимкненої анімації", "Also play the fanfare for quick picks and with the animation disabled")).changed() {
+                save_settings(&self.settings);
+            }
+            if ui.checkbox(&mut self.settings.animation.countdown, lang.pick("⏱ Відлік 3-2-1", "⏱ 3-2-1 countdown")).changed() { save_settings(&self.settings); }
+            let available = self.speaker.available();
+            let announce = ui.add_enabled(available, egui::Checkbox::new(&mut self.settings.audio.announce_winner, lang.pick("🗣 Оголошувати переможця", "🗣 Announce the winner")));
+            let announce = announce.on_hover_text(lang.pick("Зачитує назву сервера та мапу мовою інтерфейсу", "Reads out the server and map in the interface language"));
+            let announce = announce.on_disabled_hover_text(lang.pick("Синтезатор мовлення не знайдено (spd-say, espeak, say або System.Speech)", "No speech synthesizer found (spd-say, espeak, say or System.Speech)"));
+            if announce.changed() { save_settings(&self.settings); }
+            if self.settings.animation.countdown && ui.checkbox(&mut self.settings.audio.countdown_sounds, lang.pick("Звуки відліку", "Countdown sounds")).changed() { save_settings(&self.settings); }
+            ui.label(lang.pick("⏳ Тривалість, с:", "⏳ Duration, s:"));
+            let range = SPIN_DURATION_MIN..=SPIN_DURATION_MAX;
+            let min_changed = ui.add(egui::DragValue::new(&mut self.settings.animation.min_duration).range(range.clone()).speed(0.1).max_decimals(1));
+            ui.label("–");
+            let max_changed = ui.add(egui::DragValue::new(&mut self.settings.animation.max_duration).range(range).speed(0.1).max_decimals(1));
+            if min_changed.changed() || max_changed.changed() {
+                self.settings.animation.enforce_duration_bounds(min_changed.changed());
+                self.settings_dirty = true;
+            }
+            ui.label(lang.pick("Крива:", "Curve:"));
+            egui::ComboBox::from_id_salt("easing")
+                .selected_text(self.settings.animation.easing.label())
+                .show_ui(ui, |ui| {
+                    for easing in Easing::ALL {
+                        if ui.selectable_value(&mut self.settings.animation.easing, easing, easing.label()).changed() { save_settings(&self.settings); }
+                    }
+                });
+            easing_preview(ui, self.settings.animation.easing);
+            let instant = ui.checkbox(&mut self.settings.animation.instant, lang.pick("⏭ Без анімації", "⏭ Disable animation"));
+            if instant.on_hover_text(lang.pick("Результат одразу, без прокрутки колеса", "Show the result right away, without scrolling the wheel")).changed() {
+                save_settings(&self.settings);
+            }
+            let near_miss = ui.checkbox(&mut self.settings.animation.near_miss, lang.pick("😱 Майже промах", "😱 Near miss"));
+            if near_miss.on_hover_text(lang.pick("Колесо проскакує на рядок далі й повертається до переможця", "The wheel slips one row past the winner, then ticks back")).changed() {
+                save_settings(&self.settings);
+            }
+            let nudge = ui.checkbox(&mut self.settings.animation.nudge, lang.pick("🫣 Ривок", "🫣 Suspense nudge"));
+            if nudge.on_hover_text(lang.pick("Іноді колесо зупиняється за рядок до переможця і після паузи дотягує до нього", "Sometimes the wheel stops a row short and, after a pause, creeps onto the winner")).changed() {
+                save_settings(&self.settings);
+            }
+            if self.settings.animation.nudge {
+                let chance = ui.add(egui::DragValue::new(&mut self.settings.animation.nudge_percent).range(0..=100).suffix("%"));
+                if chance.on_hover_text(lang.pick("Як часто стається ривок", "How often the nudge happens")).changed() { self.settings_dirty = true; }
+            }
+            if ui.checkbox(&mut self.settings.animation.reduced_effects, lang.pick("Менше ефектів", "Reduced effects")).changed() {
+                if self.settings.animation.reduced_effects { self.particles.clear(); }
+                save_settings(&self.settings);
+            }
+            let reduced_motion = ui.checkbox(&mut self.settings.animation.reduced_motion, lang.pick("🐢 Без руху", "🐢 Reduced motion"));
+            if reduced_motion.on_hover_text(lang.pick("Замість прокрутки імена плавно змінюють одне одного на місці; без конфеті та інших ефектів", "Instead of scrolling, names fade into one another in place; no confetti or other effects")).changed() {
+                if self.settings.animation.reduced_motion { self.particles.clear(); }
+                save_settings(&self.settings);
+            }
+            #[cfg(feature = "tray")]
+            if self.tray.is_some() && ui.checkbox(&mut self.settings.appearance.minimize_to_tray, lang.pick("Згортати в трей", "Minimize to tray")).changed() {
+                save_settings(&self.settings);
+            }
+            if ui.checkbox(&mut self.settings.debug_stats, lang.pick("🐞 Лічильник кадрів", "🐞 Frame counter")).changed() { save_settings(&self.settings); }
+            let notify = ui.checkbox(&mut self.settings.notifications.enabled, lang.pick("🔔 Сповіщення", "🔔 Notifications"));
+            if notify.on_hover_text(lang.pick("Системне сповіщення про переможця, коли вікно неактивне", "System notification about the winner while the window is in the background")).changed() {
+                save_settings(&self.settings);
+            }
+            let theme = self.settings.appearance.theme;
+            if ui.button(format!("{} {}", theme.icon(), theme.label(lang))).clicked() {
+                self.settings.appearance.theme = theme.next();
+                self.theme_dirty = true;
+                save_settings(&self.settings);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(lang.pick("Пристрій:", "Output:"));
+            let default_label = lang.pick("Системний за замовчуванням", "System default");
+            let mut device = self.settings.audio.device.clone();
+            egui::ComboBox::from_id_salt("audio_device")
+                .selected_text(device.as_deref().unwrap_or(default_label))
+                .width(220.0)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut device, None, default_label);
+                    for name in AudioEngine::output_devices() { ui.selectable_value(&mut device, Some(name.clone()), name); }
+                });
+            if device != self.settings.audio.device {
+                self.settings.audio.device = device;
+                save_settings(&self.settings);
+                self.reopen_audio();
+            }
+            if ui.button(lang.pick("🔄 Перезапустити звук", "🔄 Reinitialize audio")).clicked() { self.reopen_audio(); }
+            if !self.audio.available() && !self.audio.initializing() { ui.colored_label(palette(ui.visuals()).warning, lang.pick("🔇 Звук недоступний", "🔇 Audio unavailable")); }
+        });
+        ui.horizontal(|ui| {
+            ui.label(lang.pick("Звуки:", "Sounds:"));
+            let mut theme = self.settings.audio.theme;
+            egui::ComboBox::from_id_salt("sound_theme")
+                .selected_text(sound_theme_label(theme, lang))
+                .show_ui(ui, |ui| {
+                    for option in SoundTheme::ALL { ui.selectable_value(&mut theme, option, sound_theme_label(option, lang)); }
+                });
+            if theme != self.settings.audio.theme {
+                self.settings.audio.theme = theme;
+                self.load_custom_sounds();
+                save_settings(&self.settings);
+            }
+            if ui.button("▶").on_hover_text(lang.pick("Прослухати тік і фанфари", "Preview the tick and the fanfare")).clicked() {
+                self.play_click();
+                self.play_fanfare();
+            }
+        });
+        self.sound_file_ui(ui, lang.pick("Звук тіку:", "Tick sound:"), true);
+        self.sound_file_ui(ui, lang.pick("Звук перемоги:", "Win sound:"), false);
+        let lab = ui.checkbox(&mut self.settings.audio.sound_lab, lang.pick("🧪 Звукова лабораторія", "🧪 Sound lab"));
+        if lab.on_hover_text(lang.pick("Ручне налаштування синтезу тіку", "Hand-tune how the tick is synthesized")).changed() { save_settings(&self.settings); }
+        if self.settings.audio.sound_lab { self.sound_lab_ui(ui); }
+        ui.horizontal(|ui| {
+            ui.label(lang.pick("Акцент:", "Accent:"));
+            let default_accent = palette(ui.visuals()).heading;
+            let mut color = self.settings.appearance.accent.map_or(default_accent, |[r, g, b]| egui::Color32::from_rgb(r, g, b));
+            if ui.color_edit_button_srgba(&mut color).changed() {
+                self.settings.appearance.accent = Some([color.r(), color.g(), color.b()]);
+                save_settings(&self.settings);
+            }
+            if self.settings.appearance.accent.is_some() && ui.button(lang.pick("Типовий", "Default")).clicked() {
+                self.settings.appearance.accent = None;
+                save_settings(&self.settings);
+            }
+            if ui.checkbox(&mut self.settings.appearance.row_borders, lang.pick("Рамки рядків", "Row borders")).changed() { save_settings(&self.settings); }
+        });
+        ui.horizontal(|ui| {
+            ui.label(lang.pick("Рулетка:", "Wheel:"));
+            let mut mode = self.settings.appearance.wheel_mode;
+            let idle = matches!(self.roulette_state, RouletteState::Ready | RouletteState::Finished | RouletteState::Loading);
+            ui.add_enabled_ui(idle, |ui| {
+                egui::ComboBox::from_id_salt("wheel_mode")
+                    .selected_text(mode.label(lang))
+                    .show_ui(ui, |ui| {
+                        for option in WheelMode::ALL { ui.selectable_value(&mut mode, option, option.label(lang)); }
+                    });
+            });
+            if mode != self.settings.appearance.wheel_mode { self.set_wheel_mode(mode); }
+        });
+        ui.horizontal(|ui| {
+            ui.label(lang.pick("Рядки:", "Rows:"));
+            let mut size = self.settings.appearance.row_size;
+            let idle = matches!(self.roulette_state, RouletteState::Ready | RouletteState::Finished | RouletteState::Loading);
+            ui.add_enabled_ui(idle, |ui| {
+                egui::ComboBox::from_id_salt("row_size")
+                    .selected_text(size.label(lang))
+                    .show_ui(ui, |ui| {
+                        for option in RowSize::ALL { ui.selectable_value(&mut size, option, option.label(lang)); }
+                    });
+            });
+            if size != self.settings.appearance.row_size { self.set_row_size(size); }
+        });
+        ui.horizontal(|ui| {
+            ui.label(lang.pick("Масштаб:", "UI scale:"));
+            let response = ui.add(egui::Slider::new(&mut self.settings.appearance.ui_scale, UI_SCALE_MIN..=UI_SCALE_MAX).step_by(0.05).fixed_decimals(2));
+            if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                self.apply_ui_scale(ctx);
+                save_settings(&self.settings);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(lang.pick("Шаблон Discord:", "Discord template:"));
+            let response = ui.add(egui::TextEdit::singleline(&mut self.settings.discord_template).desired_width(f32::INFINITY))
+                .on_hover_text("{name} {map} {mode} {players} {link}");
+            if response.lost_focus() { save_settings(&self.settings); }
+        });
+        ui.horizontal(|ui| {
+            if ui.button(lang.pick("🧭 Майстер", "🧭 Wizard")).clicked() { self.show_onboarding = true; }
+            if ui.add_enabled(self.can_reset(), egui::Button::new(lang.pick("↺ Скинути", "↺ Reset"))).clicked() {
+                self.show_reset_dialog = true;
+                self.confirm_full_reset = false;
+            }
+        });
+    }
+
+    pub(crate) fn reset_dialog_ui(&mut self, ctx: &egui::Context) {
+        if !self.show_reset_dialog { return; }
+        let mut open = true;
+        let mut chosen = None;
+        let lang = self.settings.language;
+        egui::Window::new(lang.pick("↺ Скинути налаштування", "↺ Reset settings"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                let colors = palette(ui.visuals());
+                ui.add_enabled_ui(self.can_reset(), |ui| {
+                    if ui.button(lang.pick("Тільки фільтри", "Filters only")).clicked() { chosen = Some(ResetScope::Filters); }
+                    if ui.button(lang.pick("Тільки звук та анімацію", "Audio and animation only")).clicked() { chosen = Some(ResetScope::AudioAnimation); }
+                    ui.separator();
+                    if !self.confirm_full_reset {
+                        if ui.button(lang.pick("Усе (включно з історією)", "Everything (including history)")).clicked() { self.confirm_full_reset = true; }
+                    } else {
+                        ui.colored_label(colors.warning, lang.pick("Це видалить усі налаштування. Впевнені?", "This removes all settings. Are you sure?"));
+                        ui.horizontal(|ui| {
+                            if ui.button(egui::RichText::new(lang.pick("Так, скинути все", "Yes, reset everything")).color(colors.danger)).clicked() { chosen = Some(ResetScope::Everything); }
+                            if ui.button(lang.pick("Скасувати", "Cancel")).clicked() { self.confirm_full_reset = false; }
+                        });
+                    }
+                });
+                if !self.can_reset() { ui.label(lang.pick("Недоступно під час оновлення або обертання.", "Unavailable while loading or spinning.")); }
+            });
+        if let Some(scope) = chosen {
+            self.reset_settings(scope);
+            open = false;
+        }
+        if !open {
+            self.show_reset_dialog = false;
+            self.confirm_full_reset = false;
+        }
+    }
+}
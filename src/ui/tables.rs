@@ -0,0 +1,152 @@
+use eframe::egui;
+
+use squad_roulette::format::format_ago;
+use squad_roulette::model::RouletteState;
+
+use crate::app::{RouletteApp, RowAction, SortColumn, MIN_HIT_SIZE};
+use crate::ui::theme::palette;
+use crate::ui::widgets::{icon_button, row_context_menu};
+
+impl RouletteApp {
+    pub(crate) fn show_history(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let lang = self.settings.language;
+        ui.label(format!("{} {}", lang.pick("Результатів за сесію:", "Results this session:"), self.history.len()));
+        if self.history.is_empty() {
+            ui.label(egui::RichText::new(lang.pick("Поки порожньо — крутіть рулетку!", "Nothing yet — give the wheel a spin!")).weak());
+            return;
+        }
+        let mut reopen = None;
+        let mut replay = None;
+        let can_replay = matches!(self.roulette_state, RouletteState::Ready | RouletteState::Finished);
+        let last_spin = self.history.iter().rposition(|e| e.spin.is_some());
+        if ui.add_enabled(can_replay && last_spin.is_some(), egui::Button::new(lang.pick("🔁 Повторити останню прокрутку", "🔁 Replay last spin"))).clicked() { replay = last_spin; }
+        egui::ScrollArea::vertical()
+            .id_salt("session_history")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for (index, entry) in self.history.iter().enumerate().rev() {
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(format_ago(entry.timestamp, lang)).weak());
+                        if entry.manual { ui.label("✋").on_hover_text(lang.pick("Обрано вручну", "Picked manually")); }
+                        ui.add(egui::Label::new(egui::RichText::new(&entry.server.name).strong()).truncate());
+                        ui.label(&entry.server.map);
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if icon_button(ui, true, "🔍", lang.pick("Показати деталі", "Show details")).clicked() { reopen = Some(index); }
+                            if let Some(spin) = &entry.spin {
+                                let label = format!("{} · seed {:016x}", lang.pick("Повторити прокрутку", "Replay spin"), spin.outcome.seed);
+                                if icon_button(ui, can_replay, "🔁", &label).clicked() { replay = Some(index); }
+                            }
+                            if icon_button(ui, true, "📋", lang.pick("Скопіювати назву", "Copy name")).clicked() { ctx.output_mut(|o| o.copied_text = entry.server.name.clone()); }
+                        });
+                    });
+                }
+            });
+        if let Some(index) = reopen { self.reopen_history_entry(index); }
+        if let Some(index) = replay { self.replay_spin(index); }
+    }
+
+    pub(crate) fn server_table_ui(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        use egui_extras::{Column, TableBuilder};
+        let lang = self.settings.language;
+        let colors = palette(ui.visuals());
+
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            ui.add(egui::TextEdit::singleline(&mut self.table_search).hint_text(lang.pick("Пошук за назвою або картою", "Search by name or map")));
+            if !self.table_search.is_empty() && icon_button(ui, true, "✖", lang.pick("Очистити пошук", "Clear search")).clicked() { self.table_search.clear(); }
+        });
+
+        let query = self.table_search.to_lowercase();
+        let mut rows: Vec<usize> = (0..self.roulette_servers.len())
+            .filter(|&i| {
+                let server = &self.roulette_servers[i];
+                query.is_empty() || server.name.to_lowercase().contains(&query) || server.map.to_lowercase().contains(&query)
+            })
+            .collect();
+        let servers = &self.roulette_servers;
+        rows.sort_by(|&a, &b| {
+            let (a, b) = (&servers[a], &servers[b]);
+            let order = match self.table_sort {
+                SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                SortColumn::Map => a.map.cmp(&b.map),
+                SortColumn::Mode => a.mode.cmp(&b.mode),
+                SortColumn::Players => a.players.cmp(&b.players),
+                SortColumn::Queue => a.queue.cmp(&b.queue),
+                SortColumn::Country => a.country.cmp(&b.country),
+            };
+            if self.table_sort_desc { order.reverse() } else { order }
+        });
+
+        let mut actions: Vec<(usize, RowAction)> = Vec::new();
+        let can_pick = self.roulette_state != RouletteState::Spinning;
+        let columns = [
+            (SortColumn::Name, lang.pick("Назва", "Name")),
+            (SortColumn::Map, lang.pick("Карта", "Map")),
+            (SortColumn::Mode, lang.pick("Режим", "Mode")),
+            (SortColumn::Players, lang.pick("Гравці", "Players")),
+            (SortColumn::Queue, lang.pick("Черга", "Queue")),
+            (SortColumn::Country, lang.pick("Країна", "Country")),
+        ];
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .sense(egui::Sense::click())
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::exact(MIN_HIT_SIZE + 4.0))
+            .column(Column::remainder().at_least(160.0).clip(true))
+            .column(Column::initial(110.0).clip(true))
+            .column(Column::initial(70.0).clip(true))
+            .column(Column::initial(70.0))
+            .column(Column::initial(55.0))
+            .column(Column::initial(60.0))
+            .column(Column::exact(4.0 * MIN_HIT_SIZE + 40.0))
+            .header(26.0, |mut header| {
+                header.col(|_| {});
+                for (column, title) in columns {
+                    header.col(|ui| {
+                        let arrow = if self.table_sort != column { "" } else if self.table_sort_desc { " ⏷" } else { " ⏶" };
+                        if ui.add(egui::Button::new(egui::RichText::new(format!("{}{}", title, arrow)).strong()).frame(false)).clicked() {
+                            if self.table_sort == column { self.table_sort_desc = !self.table_sort_desc; }
+                            else {
+                                self.table_sort = column;
+                                self.table_sort_desc = matches!(column, SortColumn::Players | SortColumn::Queue);
+                            }
+                        }
+                    });
+                }
+                header.col(|_| {});
+            })
+            .body(|body| {
+                body.rows(MIN_HIT_SIZE + 4.0, rows.len(), |mut row| {
+                    let idx = rows[row.index()];
+                    let server = &servers[idx];
+                    let details = &self.pool_details[idx];
+                    row.col(|ui| {
+                        let star = if self.lists.is_favorite(&server.id) { "⭐" } else { "☆" };
+                        if icon_button(ui, true, star, lang.pick("Улюблений", "Favorite")).clicked() { actions.push((idx, RowAction::ToggleFavorite)); }
+                    });
+                    row.col(|ui| { ui.add(egui::Label::new(egui::RichText::new(&details.title).color(colors.server_name)).truncate()).on_hover_text(&details.country); });
+                    row.col(|ui| { ui.add(egui::Label::new(&server.map).truncate()); });
+                    row.col(|ui| { ui.add(egui::Label::new(&server.mode).truncate()); });
+                    row.col(|ui| { ui.label(egui::RichText::new(format!("{}/{}", server.players, server.max_players)).color(colors.players)); });
+                    row.col(|ui| { ui.label(server.queue.to_string()); });
+                    row.col(|ui| { ui.label(&server.country); });
+                    row.col(|ui| {
+                        if icon_button(ui, can_pick, "🎯", lang.pick("Обрати переможцем", "Pick as winner")).clicked() { actions.push((idx, RowAction::PickAsWinner)); }
+                        if icon_button(ui, true, "📋", lang.pick("Скопіювати назву", "Copy name")).clicked() { actions.push((idx, RowAction::CopyName)); }
+                        if icon_button(ui, true, "🌐", lang.pick("Відкрити на BattleMetrics", "Open on BattleMetrics")).clicked() { actions.push((idx, RowAction::OpenBattleMetrics)); }
+                        if icon_button(ui, can_pick, "🚫", lang.pick("До чорного списку", "Blacklist")).clicked() { actions.push((idx, RowAction::Blacklist)); }
+                    });
+                    let favorite = self.lists.is_favorite(&server.id);
+                    row.response().context_menu(|ui| row_context_menu(ui, lang, favorite, can_pick, idx, &mut actions));
+                });
+            });
+
+        if rows.is_empty() {
+            ui.vertical_centered(|ui| { ui.label(lang.pick("Немає серверів для показу.", "No servers to show.")); });
+        }
+
+        self.apply_row_actions(ctx, actions);
+    }
+}
@@ -0,0 +1,120 @@
+use eframe::egui;
+
+use squad_roulette::model::{AppearanceSettings, Population, Theme};
+
+const MIN_ACCENT_CONTRAST: f32 = 3.0;
+
+pub(crate) fn apply_theme(ctx: &egui::Context, theme: Theme) {
+    ctx.set_theme(match theme {
+        Theme::Dark => egui::ThemePreference::Dark,
+        Theme::Light => egui::ThemePreference::Light,
+        Theme::System => egui::ThemePreference::System,
+    });
+}
+
+pub(crate) struct Palette {
+    pub(crate) heading: egui::Color32,
+    pub(crate) server_name: egui::Color32,
+    pub(crate) players: egui::Color32,
+    pub(crate) winner: egui::Color32,
+    pub(crate) ok: egui::Color32,
+    pub(crate) warning: egui::Color32,
+    pub(crate) danger: egui::Color32,
+    pub(crate) canvas_fill: egui::Color32,
+    pub(crate) canvas_stroke: egui::Color32,
+    tint_sweet: egui::Color32,
+    tint_busy: egui::Color32,
+    tint_full: egui::Color32,
+    tint_low: egui::Color32,
+}
+
+impl Palette {
+    pub(crate) fn population_color(&self, population: Population, visuals: &egui::Visuals) -> egui::Color32 {
+        match population {
+            Population::Sweet => self.ok,
+            Population::Busy => self.warning,
+            Population::Full => self.danger,
+            Population::Low => visuals.widgets.inactive.bg_fill,
+        }
+    }
+
+    pub(crate) fn population_tint(&self, population: Population) -> egui::Color32 {
+        match population {
+            Population::Sweet => self.tint_sweet,
+            Population::Busy => self.tint_busy,
+            Population::Full => self.tint_full,
+            Population::Low => self.tint_low,
+        }
+    }
+}
+
+const DARK_PALETTE: Palette = Palette {
+    heading: egui::Color32::GOLD,
+    server_name: egui::Color32::LIGHT_BLUE,
+    players: egui::Color32::YELLOW,
+    winner: egui::Color32::GREEN,
+    ok: egui::Color32::GREEN,
+    warning: egui::Color32::YELLOW,
+    danger: egui::Color32::RED,
+    canvas_fill: egui::Color32::from_black_alpha(230),
+    canvas_stroke: egui::Color32::DARK_GRAY,
+    tint_sweet: egui::Color32::from_rgba_premultiplied(0, 40, 10, 40),
+    tint_busy: egui::Color32::from_rgba_premultiplied(36, 32, 0, 40),
+    tint_full: egui::Color32::from_rgba_premultiplied(40, 20, 0, 40),
+    tint_low: egui::Color32::from_rgba_premultiplied(18, 18, 18, 40),
+};
+
+const LIGHT_PALETTE: Palette = Palette {
+    heading: egui::Color32::from_rgb(176, 120, 0),
+    server_name: egui::Color32::from_rgb(20, 70, 160),
+    players: egui::Color32::from_rgb(170, 100, 0),
+    winner: egui::Color32::from_rgb(0, 130, 40),
+    ok: egui::Color32::from_rgb(0, 130, 40),
+    warning: egui::Color32::from_rgb(190, 110, 0),
+    danger: egui::Color32::from_rgb(200, 30, 30),
+    canvas_fill: egui::Color32::from_rgb(235, 235, 235),
+    canvas_stroke: egui::Color32::GRAY,
+    tint_sweet: egui::Color32::from_rgb(212, 238, 214),
+    tint_busy: egui::Color32::from_rgb(242, 236, 200),
+    tint_full: egui::Color32::from_rgb(246, 222, 196),
+    tint_low: egui::Color32::from_rgb(222, 222, 222),
+};
+
+pub(crate) fn palette(visuals: &egui::Visuals) -> &'static Palette {
+    palette_for(visuals.dark_mode)
+}
+
+pub(crate) fn palette_for(dark_mode: bool) -> &'static Palette {
+    if dark_mode { &DARK_PALETTE } else { &LIGHT_PALETTE }
+}
+
+fn relative_luminance(color: egui::Color32) -> f32 {
+    let channel = |c: u8| {
+        let c = c as f32 / 255.0;
+        if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * channel(color.r()) + 0.7152 * channel(color.g()) + 0.0722 * channel(color.b())
+}
+
+fn contrast_ratio(a: egui::Color32, b: egui::Color32) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+pub(crate) fn contrasting_text(background: egui::Color32) -> egui::Color32 {
+    if contrast_ratio(background, egui::Color32::BLACK) >= contrast_ratio(background, egui::Color32::WHITE) { egui::Color32::BLACK } else { egui::Color32::WHITE }
+}
+
+fn readable_on(color: egui::Color32, background: egui::Color32) -> egui::Color32 {
+    let target = contrasting_text(background);
+    let mut adjusted = color.to_opaque();
+    for step in 1..=10 {
+        if contrast_ratio(adjusted, background) >= MIN_ACCENT_CONTRAST { break; }
+        adjusted = egui::lerp(egui::Rgba::from(color.to_opaque())..=egui::Rgba::from(target), step as f32 / 10.0).into();
+    }
+    adjusted
+}
+
+pub(crate) fn accent_color(appearance: &AppearanceSettings, visuals: &egui::Visuals) -> Option<egui::Color32> {
+    appearance.accent.map(|[r, g, b]| readable_on(egui::Color32::from_rgb(r, g, b), visuals.panel_fill))
+}
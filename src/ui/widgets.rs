@@ -0,0 +1,422 @@
+use eframe::egui;
+
+use squad_roulette::animation::Easing;
+use squad_roulette::audio::SoundTheme;
+use squad_roulette::format::render_discord_message;
+use squad_roulette::model::{
+    country_flag, country_name, Language, Population, ServerItem, Settings, SpinBlocker, ROW_HEIGHT,
+};
+
+use crate::app::{RowAction, MIN_HIT_SIZE};
+use crate::ui::theme::{palette, palette_for, Palette};
+
+const BLUR_SPEED: f32 = 1200.0;
+pub(crate) const FOCUS_MAX_SCALE: f32 = 1.1;
+const FOCUS_MIN_OPACITY: f32 = 0.45;
+const EDGE_FADE_DEPTH: f32 = 40.0;
+const PLACEHOLDER_ROWS: usize = 4;
+const SHIMMER_PERIOD: f32 = 1.4;
+const MIN_VISIBLE_ROWS: f32 = 3.0;
+const MAX_VISIBLE_ROWS: f32 = 9.0;
+const WINNER_PANEL_RESERVE: f32 = 300.0;
+const COMPACT_HEIGHT_THRESHOLD: f32 = 820.0;
+
+// The samples as a centre line with the wave drawn around it, one point per column of pixels.
+pub(crate) fn paint_waveform(ui: &mut egui::Ui, samples: &[f32]) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width().min(360.0), 60.0), egui::Sense::hover());
+    let colors = palette(ui.visuals());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 4.0, colors.canvas_fill);
+    painter.hline(rect.x_range(), rect.center().y, egui::Stroke::new(1.0, colors.canvas_stroke));
+    if samples.is_empty() { return; }
+    let columns = rect.width().max(1.0) as usize;
+    let points: Vec<egui::Pos2> = (0..columns)
+        .map(|column| {
+            let sample = samples[column * samples.len() / columns];
+            egui::pos2(rect.left() + column as f32, rect.center().y - sample.clamp(-1.0, 1.0) * rect.height() * 0.5)
+        })
+        .collect();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.0, colors.heading)));
+}
+
+pub(crate) fn sound_theme_label(theme: SoundTheme, lang: Language) -> &'static str {
+    match theme {
+        SoundTheme::Mechanical => lang.pick("⚙ Механічні", "⚙ Mechanical"),
+        SoundTheme::Casino => lang.pick("🎰 Казино", "🎰 Casino"),
+        SoundTheme::Soft => lang.pick("☁ М'які", "☁ Soft"),
+    }
+}
+
+pub(crate) fn gated_button(ui: &mut egui::Ui, blocker: Option<SpinBlocker>, button: egui::Button<'_>, lang: Language) -> egui::Response {
+    let response = ui.add_enabled(blocker.is_none(), button);
+    match blocker {
+        Some(blocker) => response.on_disabled_hover_text(blocker.reason(lang)),
+        None => response,
+    }
+}
+
+pub(crate) fn slider_with_value(ui: &mut egui::Ui, value: &mut u32, range: std::ops::RangeInclusive<u32>, label: &str) -> egui::Response {
+    ui.horizontal(|ui| {
+        let slider = ui.add(egui::Slider::new(value, range.clone()).show_value(false));
+        let drag = ui.add(egui::DragValue::new(value).range(range).speed(0.25));
+        ui.label(label);
+        let current = *value as f64;
+        slider.widget_info(|| egui::WidgetInfo::slider(true, current, label));
+        drag.widget_info(|| egui::WidgetInfo::slider(true, current, label));
+        slider | drag
+    }).inner
+}
+
+pub(crate) fn icon_button(ui: &mut egui::Ui, enabled: bool, icon: &str, label: &str) -> egui::Response {
+    let response = ui.add_enabled(enabled, egui::Button::new(icon).min_size(egui::vec2(MIN_HIT_SIZE, MIN_HIT_SIZE)));
+    response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, enabled, label));
+    response.on_hover_text(label)
+}
+
+pub(crate) fn row_context_menu(ui: &mut egui::Ui, lang: Language, favorite: bool, can_edit: bool, idx: usize, actions: &mut Vec<(usize, RowAction)>) {
+    let mut item = |ui: &mut egui::Ui, enabled: bool, text: &str, action: RowAction| {
+        if ui.add_enabled(enabled, egui::Button::new(text)).clicked() {
+            actions.push((idx, action));
+            ui.close_menu();
+        }
+    };
+    item(ui, true, lang.pick("📋 Скопіювати назву", "📋 Copy name"), RowAction::CopyName);
+    item(ui, true, lang.pick("📄 Скопіювати інфо", "📄 Copy info"), RowAction::CopyInfo);
+    ui.separator();
+    let favorite_text = if favorite { lang.pick("☆ Прибрати з улюблених", "☆ Remove from favorites") } else { lang.pick("⭐ Додати до улюблених", "⭐ Add to favorites") };
+    item(ui, true, favorite_text, RowAction::ToggleFavorite);
+    item(ui, can_edit, lang.pick("🚫 До чорного списку", "🚫 Add to blacklist"), RowAction::Blacklist);
+    item(ui, can_edit, lang.pick("✖ Прибрати на цю сесію", "✖ Exclude for this session"), RowAction::ExcludeForSession);
+    ui.separator();
+    item(ui, true, lang.pick("🌐 Відкрити на BattleMetrics", "🌐 Open on BattleMetrics"), RowAction::OpenBattleMetrics);
+}
+
+// Everything a row draws that has to be formatted or looked up, done once per pool change instead of
+// every frame. The strings follow the language and the tint the palette, so either changing rebuilds it too.
+pub(crate) struct ServerDetails {
+    pub(crate) title: String,
+    // Characters in the title, for sizing the silhouette drawn at full speed.
+    title_len: usize,
+    tint: egui::Color32,
+    map_line: String,
+    seats: String,
+    pub(crate) summary: String,
+    players: String,
+    pub(crate) country: String,
+    rank: Option<String>,
+    pub(crate) population: Population,
+}
+
+impl ServerDetails {
+    pub(crate) fn new(server: &ServerItem, lang: Language, colors: &Palette) -> Self {
+        let population = Population::of(server);
+        let title = format!("{} {}", country_flag(&server.country), server.name);
+        Self {
+            population,
+            title_len: title.chars().count(),
+            tint: colors.population_tint(population),
+            title,
+            map_line: format!("🗺️ {}", server.map),
+            seats: format!("👥 {}/{}", server.players, server.max_players),
+            summary: format!("🗺️ {}   👥 {}", server.map, server.players_text()),
+            players: server.players_text(),
+            country: format!("{} {} ({})", country_flag(&server.country), country_name(&server.country, lang).unwrap_or(&server.country), server.country),
+            rank: server.rank.map(|rank| format!("#{}", rank)),
+        }
+    }
+}
+
+pub(crate) fn server_details_grid(ui: &mut egui::Ui, id_salt: &str, server: &ServerItem, details: &ServerDetails, lang: Language) {
+    let colors = palette(ui.visuals());
+    egui::Grid::new(id_salt).num_columns(2).spacing([16.0, 6.0]).show(ui, |ui| {
+        ui.label(lang.pick("🗺️ Карта", "🗺️ Map"));
+        ui.label(egui::RichText::new(&server.map).italics());
+        ui.end_row();
+        ui.label(lang.pick("🎮 Режим", "🎮 Mode"));
+        ui.label(&server.mode);
+        ui.end_row();
+        ui.label(lang.pick("👥 Гравці", "👥 Players"));
+        ui.label(egui::RichText::new(&details.players).color(colors.players));
+        ui.end_row();
+        ui.label(lang.pick("🏳 Країна", "🏳 Country"));
+        ui.label(&details.country);
+        ui.end_row();
+        if let Some(rank) = &details.rank {
+            ui.label(lang.pick("🏆 Ранг BM", "🏆 BM rank"));
+            ui.label(rank);
+            ui.end_row();
+        }
+        if server.licensed {
+            ui.label(lang.pick("✔ Ліцензований", "✔ Licensed"));
+            ui.label(egui::RichText::new(lang.pick("так", "yes")).color(colors.ok));
+            ui.end_row();
+        }
+    });
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct LayoutMetrics {
+    pub(crate) compact: bool,
+    pub(crate) section_gap: f32,
+    pub(crate) small_gap: f32,
+    pub(crate) button_height: f32,
+}
+
+impl LayoutMetrics {
+    pub(crate) fn for_height(height: f32) -> Self {
+        if height < COMPACT_HEIGHT_THRESHOLD {
+            Self { compact: true, section_gap: 6.0, small_gap: 4.0, button_height: 40.0 }
+        } else {
+            Self { compact: false, section_gap: 20.0, small_gap: 10.0, button_height: 60.0 }
+        }
+    }
+
+    // Bounds are in rows of the current size, so large rows still show three and compact ones stop at nine.
+    pub(crate) fn scroll_height(&self, available: f32, row_height: f32) -> f32 {
+        if self.compact { return row_height * MIN_VISIBLE_ROWS; }
+        (available - WINNER_PANEL_RESERVE).clamp(row_height * MIN_VISIBLE_ROWS, row_height * MAX_VISIBLE_ROWS)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RowDetail {
+    Full,
+    Bars,
+}
+
+impl RowDetail {
+    pub(crate) fn for_speed(speed: f32) -> Self {
+        if speed > BLUR_SPEED { RowDetail::Bars } else { RowDetail::Full }
+    }
+}
+
+// Scale and opacity for an item `distance` pitches away from the pointer: ~10% larger right under it, tapering off.
+pub(crate) fn focus_emphasis(distance: f32) -> (f32, f32) {
+    let scale = FOCUS_MAX_SCALE / (1.0 + 0.1 * distance);
+    let opacity = (1.0 / (1.0 + 0.25 * distance)).max(FOCUS_MIN_OPACITY);
+    (scale, opacity)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RowLook {
+    pub(crate) detail: RowDetail,
+    pub(crate) height: f32,
+    pub(crate) text_scale: f32,
+    pub(crate) borders: bool,
+    pub(crate) shaded: bool,
+}
+
+fn row_fill(ui: &egui::Ui, details: Option<&ServerDetails>, shaded: bool) -> egui::Color32 {
+    let fill = details.map_or(ui.visuals().faint_bg_color, |d| d.tint);
+    if shaded { fill.gamma_multiply(0.6) } else { fill }
+}
+
+pub(crate) fn wheel_row_ui(ui: &mut egui::Ui, server: &ServerItem, details: Option<&ServerDetails>, colors: &Palette, look: RowLook) -> egui::Response {
+    let RowLook { detail, height, text_scale, borders, shaded } = look;
+    let fill = row_fill(ui, details, shaded);
+    let title = details.map_or(&server.name, |d| &d.title);
+    if detail == RowDetail::Bars {
+        // Nothing is readable at this speed, so skip text layout and draw the row's silhouette instead.
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width() - 10.0, height - 8.0), egui::Sense::hover());
+        let painter = ui.painter();
+        painter.rect_filled(rect, 6.0, fill);
+        let name_width = (details.map_or_else(|| title.chars().count(), |d| d.title_len) as f32 * 11.0 * text_scale).min(rect.width() - 20.0);
+        painter.rect_filled(egui::Rect::from_center_size(rect.center() - egui::vec2(0.0, rect.height() * 0.14), egui::vec2(name_width, 12.0 * text_scale)), 4.0, colors.server_name.gamma_multiply(0.7));
+        painter.rect_filled(egui::Rect::from_center_size(rect.center() + egui::vec2(0.0, rect.height() * 0.19), egui::vec2(name_width * 0.6, 8.0 * text_scale)), 3.0, colors.players.gamma_multiply(0.5));
+        return response;
+    }
+    let mut frame = egui::Frame::group(ui.style()).fill(fill);
+    if !borders { frame = frame.stroke(egui::Stroke::NONE); }
+    frame.show(ui, |ui| {
+        ui.set_width(ui.available_width() - 10.0);
+        ui.vertical_centered(|ui| {
+            ui.add_space(2.0); 
+            ui.add(egui::Label::new(egui::RichText::new(title).size(20.0 * text_scale).strong().color(colors.server_name)).truncate());
+            ui.horizontal_centered(|ui| {
+                ui.label(egui::RichText::new(details.map_or_else(|| format!("🗺️ {}", server.map), |d| d.map_line.clone())).size(14.0 * text_scale));
+                ui.add_space(10.0);
+                ui.label(egui::RichText::new(details.map_or_else(|| format!("👥 {}/{}", server.players, server.max_players), |d| d.seats.clone())).size(14.0 * text_scale).color(colors.players));
+            });
+        });
+    }).response
+}
+
+pub(crate) fn wheel_card_ui(ui: &mut egui::Ui, server: &ServerItem, details: Option<&ServerDetails>, colors: &Palette, look: RowLook) -> egui::Response {
+    let RowLook { detail, text_scale, borders, shaded, .. } = look;
+    let fill = row_fill(ui, details, shaded);
+    let title = details.map_or(&server.name, |d| &d.title);
+    let card = ui.max_rect().shrink(4.0);
+    if detail == RowDetail::Bars {
+        let response = ui.allocate_rect(card, egui::Sense::hover());
+        let painter = ui.painter();
+        painter.rect_filled(card, 6.0, fill);
+        let name_width = (details.map_or_else(|| title.chars().count(), |d| d.title_len) as f32 * 9.0 * text_scale).min(card.width() - 20.0);
+        painter.rect_filled(egui::Rect::from_center_size(card.center() - egui::vec2(0.0, 18.0), egui::vec2(name_width, 12.0)), 4.0, colors.server_name.gamma_multiply(0.7));
+        painter.rect_filled(egui::Rect::from_center_size(card.center() + egui::vec2(0.0, 10.0), egui::vec2(name_width * 0.6, 8.0)), 3.0, colors.players.gamma_multiply(0.5));
+        return response;
+    }
+    let mut frame = egui::Frame::group(ui.style()).fill(fill);
+    if !borders { frame = frame.stroke(egui::Stroke::NONE); }
+    let margins = frame.inner_margin.sum() + egui::vec2(frame.stroke.width, frame.stroke.width) * 2.0;
+    ui.allocate_new_ui(egui::UiBuilder::new().max_rect(card), |ui| {
+        frame.show(ui, |ui| {
+            ui.set_min_size(card.size() - margins);
+            ui.set_max_width(card.width() - margins.x);
+            ui.vertical_centered(|ui| {
+                ui.add(egui::Label::new(egui::RichText::new(title).size(18.0 * text_scale).strong().color(colors.server_name)).truncate());
+                ui.add(egui::Label::new(egui::RichText::new(details.map_or_else(|| format!("🗺️ {}", server.map), |d| d.map_line.clone())).size(13.0 * text_scale)).truncate());
+                ui.label(egui::RichText::new(details.map_or_else(|| format!("👥 {}/{}", server.players, server.max_players), |d| d.seats.clone())).size(13.0 * text_scale).color(colors.players));
+            });
+        }).response
+    }).inner
+}
+
+// Rows emerge from the canvas colour at the top and bottom edges instead of popping in.
+pub(crate) fn paint_edge_fade(painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
+    let depth = EDGE_FADE_DEPTH.min(rect.height() * 0.15);
+    let mut mesh = egui::Mesh::default();
+    for (edge, inner) in [(rect.top(), rect.top() + depth), (rect.bottom(), rect.bottom() - depth)] {
+        let base = mesh.vertices.len() as u32;
+        mesh.colored_vertex(egui::pos2(rect.left(), edge), color);
+        mesh.colored_vertex(egui::pos2(rect.right(), edge), color);
+        mesh.colored_vertex(egui::pos2(rect.left(), inner), egui::Color32::TRANSPARENT);
+        mesh.colored_vertex(egui::pos2(rect.right(), inner), egui::Color32::TRANSPARENT);
+        mesh.add_triangle(base, base + 1, base + 2);
+        mesh.add_triangle(base + 1, base + 2, base + 3);
+    }
+    painter.add(mesh);
+}
+
+pub(crate) fn paint_winner_glow(painter: &egui::Painter, item: egui::Rect, t: f32, color: egui::Color32) {
+    let item = item.expand(4.0 * t);
+    for glow in 1..=4 {
+        let alpha = t * 0.25 / glow as f32;
+        painter.rect_stroke(item.expand(glow as f32 * 3.0), 10.0, egui::Stroke::new(3.0, color.gamma_multiply(alpha)));
+    }
+    painter.rect_stroke(item, 8.0, egui::Stroke::new(3.0, color.gamma_multiply(t.sqrt())));
+}
+
+pub(crate) fn easing_preview(ui: &mut egui::Ui, easing: Easing) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(48.0, 28.0), egui::Sense::hover());
+    let painter = ui.painter();
+    painter.rect_filled(rect, 3.0, ui.visuals().extreme_bg_color);
+    // Leave headroom so an overshooting curve stays visible.
+    let plot = rect.shrink2(egui::vec2(3.0, 5.0));
+    let points = (0..=24).map(|i| {
+        let t = i as f32 / 24.0;
+        egui::pos2(plot.left() + t * plot.width(), plot.bottom() - easing.apply(t) * plot.height())
+    }).collect();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, ui.visuals().strong_text_color())));
+}
+
+pub(crate) fn paint_loading_rows(ui: &egui::Ui, rect: egui::Rect, row_height: f32, streamed: &[ServerItem], colors: &Palette, shimmer: bool) {
+    let painter = ui.painter();
+    let base = ui.visuals().widgets.inactive.bg_fill;
+    let highlight = ui.visuals().widgets.hovered.bg_fill;
+    let time = ui.input(|i| i.time) as f32;
+    let scale = row_height / ROW_HEIGHT;
+    let first_top = rect.center().y - row_height * PLACEHOLDER_ROWS as f32 / 2.0;
+    for i in 0..PLACEHOLDER_ROWS {
+        let row = egui::Rect::from_min_size(egui::pos2(rect.left() + 10.0, first_top + i as f32 * row_height + 6.0), egui::vec2(rect.width() - 20.0, row_height - 12.0));
+        if let Some(server) = streamed.get(i) {
+            painter.rect_filled(row, 8.0, ui.visuals().faint_bg_color);
+            painter.text(row.center_top() + egui::vec2(0.0, 20.0 * scale), egui::Align2::CENTER_CENTER, &server.name, egui::FontId::proportional(18.0 * scale), colors.server_name);
+            painter.text(row.center_bottom() - egui::vec2(0.0, 16.0 * scale), egui::Align2::CENTER_CENTER,
+                format!("🗺️ {}   👥 {}", server.map, server.players_text()), egui::FontId::proportional(14.0 * scale), colors.players);
+            continue;
+        }
+        painter.rect_filled(row, 8.0, base.gamma_multiply(0.6));
+        if !shimmer { continue; }
+        // A soft band sweeping left to right, staggered per row.
+        let phase = ((time + i as f32 * 0.15) % SHIMMER_PERIOD) / SHIMMER_PERIOD;
+        let band_center = row.left() + (phase * 1.4 - 0.2) * row.width();
+        let band_width = row.width() * 0.25;
+        let clipped = painter.with_clip_rect(row);
+        const SLICES: usize = 12;
+        for slice in 0..SLICES {
+            let offset = (slice as f32 + 0.5) / SLICES as f32 - 0.5;
+            let x = band_center + offset * band_width;
+            let strength = 1.0 - (offset * 2.0).abs();
+            let slice_rect = egui::Rect::from_min_max(egui::pos2(x - band_width / SLICES as f32 / 2.0, row.top()), egui::pos2(x + band_width / SLICES as f32 / 2.0, row.bottom()));
+            clipped.rect_filled(slice_rect, 0.0, highlight.gamma_multiply(0.5 * strength));
+        }
+    }
+}
+
+pub(crate) fn paint_replay_watermark(painter: &egui::Painter, rect: egui::Rect, colors: &Palette) {
+    painter.text(rect.center(), egui::Align2::CENTER_CENTER, "REPLAY", egui::FontId::proportional((rect.height() * 0.5).clamp(24.0, 72.0)), colors.warning.gamma_multiply(0.25));
+}
+
+// The details of a server drawn outside the pool rows, such as the winner card, kept until the server,
+// the language or the palette changes rather than formatted again each frame.
+#[derive(Default)]
+pub(crate) struct CachedDetails(Option<((ServerItem, Language, bool), ServerDetails)>);
+
+impl CachedDetails {
+    pub(crate) fn get(&mut self, server: &ServerItem, lang: Language, dark_mode: bool) -> &ServerDetails {
+        let fresh = matches!(&self.0, Some(((cached, l, d), _)) if cached == server && *l == lang && *d == dark_mode);
+        if !fresh { self.0 = None; }
+        &self.0.get_or_insert_with(|| ((server.clone(), lang, dark_mode), ServerDetails::new(server, lang, palette_for(dark_mode)))).1
+    }
+}
+
+pub(crate) fn winner_card_ui(ui: &mut egui::Ui, ctx: &egui::Context, winner: &ServerItem, details: &ServerDetails, settings: &Settings, reveal: f32, manual: bool) -> Option<String> {
+    let lang = settings.language;
+    let colors = palette(ui.visuals());
+    let mut open_url = None;
+    ui.vertical_centered(|ui| {
+        ui.multiply_opacity(reveal);
+        ui.add_space((1.0 - reveal) * 12.0);
+        ui.group(|ui| {
+            ui.set_min_width(300.0); 
+            if manual {
+                ui.label(egui::RichText::new(lang.pick("✋ ОБРАНО ВРУЧНУ:", "✋ PICKED MANUALLY:")).size(16.0));
+            } else {
+                ui.label(egui::RichText::new(lang.pick("🎉 ПЕРЕМОЖЕЦЬ:", "🎉 WINNER:")).size(16.0));
+            }
+            ui.add_space(5.0);
+            ui.add(egui::Label::new(egui::RichText::new(&winner.name).size(24.0 * (0.9 + 0.1 * reveal)).color(colors.winner).strong()).truncate())
+                .on_hover_text(&winner.name);
+            ui.add_space(5.0);
+            server_details_grid(ui, "winner_details", winner, details, lang);
+            ui.add_space(10.0);
+            ui.horizontal(|ui| open_url = winner_actions_ui(ui, ctx, winner, settings, false));
+        });
+    });
+    open_url
+}
+
+pub(crate) fn winner_strip_ui(ui: &mut egui::Ui, ctx: &egui::Context, winner: &ServerItem, settings: &Settings, reveal: f32, manual: bool) -> Option<String> {
+    let lang = settings.language;
+    let colors = palette(ui.visuals());
+    let mut open_url = None;
+    ui.multiply_opacity(reveal);
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            if manual { ui.label("✋").on_hover_text(lang.pick("Обрано вручну", "Picked manually")); } else { ui.label("🎉"); }
+            ui.add(egui::Label::new(egui::RichText::new(&winner.name).size(16.0).color(colors.winner).strong()).truncate())
+                .on_hover_text(winner.info_text(lang));
+        });
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format!("{} · {} · {}", winner.map, winner.mode, winner.players_text())).color(colors.players));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| open_url = winner_actions_ui(ui, ctx, winner, settings, true));
+        });
+    });
+    open_url
+}
+
+fn winner_actions_ui(ui: &mut egui::Ui, ctx: &egui::Context, winner: &ServerItem, settings: &Settings, compact: bool) -> Option<String> {
+    let lang = settings.language;
+    let mut open_url = None;
+    let action = |ui: &mut egui::Ui, icon: &str, text: &str| if compact { icon_button(ui, true, icon, text) } else { ui.button(format!("{} {}", icon, text)) };
+    if action(ui, "📋", lang.pick("Скопіювати назву", "Copy name")).clicked() { ctx.output_mut(|o| o.copied_text = winner.name.clone()); }
+    if action(ui, "📄", lang.pick("Скопіювати інфо", "Copy info")).clicked() { ctx.output_mut(|o| o.copied_text = winner.info_text(lang)); }
+    if action(ui, "💬", lang.pick("Для Discord", "Copy for Discord")).clicked() {
+        ctx.output_mut(|o| o.copied_text = render_discord_message(&settings.discord_template, winner));
+    }
+    if let Some(url) = winner.join_url() {
+        if action(ui, "▶", lang.pick("Приєднатися", "Join")).clicked() { ctx.open_url(egui::OpenUrl::same_tab(url)); }
+    }
+    if action(ui, "🌐", "BattleMetrics").clicked() { open_url = Some(winner.battlemetrics_url()); }
+    open_url
+}
@@ -0,0 +1,101 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use tracing::{debug, warn};
+
+// How long closing the app waits for a job that's already running before leaving it behind.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Jobs of one kind share a generation; starting a newer one makes the ones before it stale. A stale job
+// still queued is dropped without running, and a running one can notice between steps and stop early.
+#[derive(Clone, Default)]
+pub struct Generation(Arc<AtomicU64>);
+
+impl Generation {
+    pub fn advance(&self) -> Ticket {
+        let generation = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+        Ticket { generation, latest: self.0.clone() }
+    }
+}
+
+pub struct Ticket {
+    generation: u64,
+    latest: Arc<AtomicU64>,
+}
+
+impl Ticket {
+    pub fn is_current(&self) -> bool {
+        self.latest.load(Ordering::SeqCst) == self.generation
+    }
+}
+
+struct Job {
+    ticket: Ticket,
+    run: Box<dyn FnOnce(&Ticket) + Send>,
+}
+
+// The one background thread the app's slow work (network requests, mostly) runs on, so each refresh
+// doesn't cost a new OS thread and jobs queue behind each other instead of racing. Results go back
+// over whatever channel the job was given.
+pub struct Worker {
+    jobs: Option<Sender<Job>>,
+    stopping: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Worker {
+    pub fn new() -> Self {
+        let (jobs, queue) = channel::<Job>();
+        let stopping = Arc::new(AtomicBool::new(false));
+        let thread_stopping = stopping.clone();
+        let thread = thread::Builder::new()
+            .name("worker".into())
+            .spawn(move || {
+                for job in queue {
+                    if thread_stopping.load(Ordering::SeqCst) { break; }
+                    if !job.ticket.is_current() {
+                        debug!("skipping stale job from generation {}", job.ticket.generation);
+                        continue;
+                    }
+                    (job.run)(&job.ticket);
+                }
+            })
+            .map_err(|e| warn!("failed to start the worker thread: {}", e))
+            .ok();
+        Self { jobs: Some(jobs), stopping, thread }
+    }
+
+    // Returns false when the worker isn't running, in which case the job is dropped.
+    pub fn submit(&self, ticket: Ticket, run: impl FnOnce(&Ticket) + Send + 'static) -> bool {
+        let Some(jobs) = &self.jobs else { return false; };
+        jobs.send(Job { ticket, run: Box::new(run) }).is_ok()
+    }
+
+    // Queued jobs are dropped; a running one gets SHUTDOWN_TIMEOUT to finish before it's left to the OS.
+    pub fn shutdown(&mut self) {
+        self.stopping.store(true, Ordering::SeqCst);
+        self.jobs = None;
+        let Some(thread) = self.thread.take() else { return; };
+        let (done_tx, done_rx) = channel();
+        thread::spawn(move || {
+            let _ = thread.join();
+            let _ = done_tx.send(());
+        });
+        if done_rx.recv_timeout(SHUTDOWN_TIMEOUT).is_err() { warn!("worker still busy after {:?}, not waiting for it", SHUTDOWN_TIMEOUT); }
+    }
+}
+
+impl Default for Worker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,78 @@
+// BattleMetrics pages as parse_page sees them. The fixtures follow the shape of the API's /servers
+// responses: a first page, the last page (no next link), a page with entries that don't fit and an
+// error body.
+
+use squad_roulette::api::parse_page;
+
+const FIRST_PAGE: &[u8] = include_bytes!("fixtures/servers_first_page.json");
+const LAST_PAGE: &[u8] = include_bytes!("fixtures/servers_last_page.json");
+const ODD_ENTRIES: &[u8] = include_bytes!("fixtures/servers_odd_entries.json");
+const RATE_LIMITED: &[u8] = include_bytes!("fixtures/error_rate_limited.json");
+
+#[test]
+fn first_page_parses_every_server() {
+    let page = parse_page(FIRST_PAGE).unwrap();
+    assert_eq!(page.servers.len(), 6);
+    assert_eq!(page.skipped, 0);
+    let top = &page.servers[0];
+    assert_eq!(top.id, "26143592");
+    assert_eq!(top.name, "[EU] Kyiv Battalion | Vanilla | discord.gg/kyivbattalion");
+    assert_eq!((top.players, top.max_players, top.queue), (100, 100, 14));
+    assert_eq!((top.map.as_str(), top.mode.as_str(), top.country.as_str()), ("Yehorivka AAS v2", "AAS", "UA"));
+    assert_eq!(top.rank, Some(3));
+    assert!(top.licensed);
+    assert_eq!(top.join_url().as_deref(), Some("steam://connect/185.207.214.41:7787"));
+    assert_eq!(top.battlemetrics_url(), "https://www.battlemetrics.com/servers/squad/26143592");
+    assert!(!page.servers[5].licensed);
+}
+
+#[test]
+fn first_page_links_to_the_next() {
+    let next = parse_page(FIRST_PAGE).unwrap().next.expect("the first page has a next link");
+    assert!(next.starts_with("https://api.battlemetrics.com/servers?"));
+    assert!(next.contains("page%5Bkey%5D="));
+}
+
+#[test]
+fn last_page_has_no_next_link() {
+    let page = parse_page(LAST_PAGE).unwrap();
+    assert_eq!(page.next, None);
+    assert_eq!(page.servers.len(), 3);
+    assert_eq!(page.servers.iter().map(|s| s.players).collect::<Vec<_>>(), vec![3, 1, 0]);
+    assert_eq!(page.servers[1].max_players, 80);
+    assert_eq!(page.servers[0].name, "[FR] Les Irréductibles | Seed");
+}
+
+#[test]
+fn odd_entries_are_skipped_without_losing_the_page() {
+    // Missing maxPlayers, players as a string, no details and null attributes each drop only their own server.
+    let page = parse_page(ODD_ENTRIES).unwrap();
+    assert_eq!(page.skipped, 4);
+    assert_eq!(page.servers.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["27006309", "25713370"]);
+    assert!(page.next.is_some());
+}
+
+#[test]
+fn missing_optional_fields_fall_back_to_defaults() {
+    let page = parse_page(ODD_ENTRIES).unwrap();
+    let fresh = &page.servers[1];
+    assert_eq!((fresh.map.as_str(), fresh.mode.as_str(), fresh.country.as_str()), ("Unknown", "Unknown", "??"));
+    assert_eq!((fresh.queue, fresh.rank, fresh.licensed), (0, None, false));
+    assert_eq!(fresh.join_url(), None);
+}
+
+#[test]
+fn an_error_body_is_not_a_page() {
+    assert!(parse_page(RATE_LIMITED).is_err());
+    assert!(parse_page(&b"<html>502 Bad Gateway</html>"[..]).is_err());
+    assert!(parse_page(&FIRST_PAGE[..FIRST_PAGE.len() / 2]).is_err());
+}
+
+#[test]
+fn an_empty_page_is_still_a_page() {
+    let page = parse_page(&br#"{"data": [], "links": {"next": null}}"#[..]).unwrap();
+    assert!(page.servers.is_empty());
+    assert_eq!((page.skipped, page.next), (0, None));
+    // links is optional; without it there is no next page.
+    assert_eq!(parse_page(&br#"{"data": []}"#[..]).unwrap().next, None);
+}
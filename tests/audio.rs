@@ -0,0 +1,73 @@
+use std::time::{Duration, Instant};
+
+use squad_roulette::audio::{synthesize_click, ClickParams, FailureCounter, SoundTheme, LOST_AFTER_FAILURES, SAMPLE_RATE};
+
+#[test]
+fn the_output_is_lost_after_enough_drops_over_a_second() {
+    let start = Instant::now();
+    let mut counter = FailureCounter::default();
+    for i in 1..LOST_AFTER_FAILURES {
+        assert!(!counter.failed(start + Duration::from_millis(100 * i as u64)), "drop {i}");
+    }
+    let now = start + Duration::from_secs(5);
+    assert!(!counter.lost(now));
+    assert!(counter.failed(now));
+    assert!(counter.lost(now));
+    // The warning goes out once; later drops don't repeat it.
+    assert!(!counter.failed(now + Duration::from_millis(10)));
+    assert!(counter.lost(now + Duration::from_millis(10)));
+}
+
+#[test]
+fn a_sound_getting_through_resets_the_count() {
+    let start = Instant::now();
+    let mut counter = FailureCounter::default();
+    for i in 0..LOST_AFTER_FAILURES { counter.failed(start + Duration::from_millis(50 * i as u64)); }
+    assert!(counter.lost(start + Duration::from_secs(2)));
+    counter.succeeded();
+    assert!(!counter.lost(start + Duration::from_secs(2)));
+    // Counting starts over, both the drops and the time since the first of them.
+    for i in 1..LOST_AFTER_FAILURES { assert!(!counter.failed(start + Duration::from_secs(3) + Duration::from_millis(i as u64))); }
+    assert!(!counter.failed(start + Duration::from_secs(3) + Duration::from_millis(500)));
+    assert!(counter.failed(start + Duration::from_millis(4100)));
+}
+
+#[test]
+fn fast_ticks_filling_every_sink_are_not_a_lost_device() {
+    // A fast spin at a high frame rate: every sink busy for far more than LOST_AFTER_FAILURES ticks in a
+    // row, but a sink frees up well within a second.
+    let start = Instant::now();
+    let mut counter = FailureCounter::default();
+    for burst in 0..20u64 {
+        let burst_start = start + Duration::from_millis(150 * burst);
+        for tick in 0..LOST_AFTER_FAILURES as u64 * 2 {
+            let now = burst_start + Duration::from_micros(500 * tick);
+            assert!(!counter.failed(now), "burst {burst}, tick {tick}");
+            assert!(!counter.lost(now), "burst {burst}, tick {tick}");
+        }
+        counter.succeeded();
+    }
+}
+
+#[test]
+fn every_theme_click_has_its_length_and_never_clips() {
+    for theme in SoundTheme::ALL {
+        let params = theme.click();
+        for _ in 0..20 {
+            let samples = synthesize_click(params);
+            assert_eq!(samples.len() as u32, SAMPLE_RATE * params.duration_ms / 1000, "{theme:?}");
+            let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+            assert!(samples.iter().all(|s| s.is_finite()), "{theme:?}");
+            assert!(peak <= params.gain + 1e-6, "{theme:?} peaks at {peak}, past its gain {}", params.gain);
+            assert!(peak > params.gain * 0.1, "{theme:?} is nearly silent at {peak}");
+        }
+    }
+}
+
+#[test]
+fn out_of_range_sound_lab_settings_still_stay_under_full_scale() {
+    let loud = ClickParams { duration_ms: 40, smoothing: 0.0, decay_power: 0.0, gain: 3.0, tone_hz: 5000.0, tone_mix: 2.0 };
+    let samples = synthesize_click(&loud);
+    assert_eq!(samples.len(), 1764);
+    assert!(samples.iter().all(|s| s.abs() <= 1.0 + 1e-6));
+}
@@ -0,0 +1,19 @@
+use squad_roulette::model::ServerItem;
+
+// A full-enough server for the logic under test; everything not given is a plausible placeholder.
+pub fn server(id: &str, country: &str, players: u32) -> ServerItem {
+    ServerItem {
+        id: id.to_string(),
+        name: format!("Server {}", id),
+        players,
+        max_players: 100,
+        map: "Narva".to_string(),
+        mode: "RAAS".to_string(),
+        country: country.to_string(),
+        queue: 0,
+        ip: String::new(),
+        port: 0,
+        rank: None,
+        licensed: true,
+    }
+}
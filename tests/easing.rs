@@ -0,0 +1,64 @@
+use squad_roulette::animation::{Easing, SpinAnimation, SpinPhase};
+
+#[test]
+fn every_easing_runs_from_zero_to_one() {
+    for easing in Easing::ALL {
+        assert!(easing.apply(0.0).abs() < 1e-6, "{:?}", easing);
+        assert!((easing.apply(1.0) - 1.0).abs() < 1e-6, "{:?}", easing);
+        // Out-of-range progress is clamped rather than extrapolated.
+        assert_eq!(easing.apply(-1.0), easing.apply(0.0));
+        assert_eq!(easing.apply(2.0), easing.apply(1.0));
+    }
+}
+
+#[test]
+fn only_back_out_overshoots() {
+    for easing in Easing::ALL {
+        let samples: Vec<f32> = (0..=100).map(|i| easing.apply(i as f32 / 100.0)).collect();
+        let peak = samples.iter().cloned().fold(f32::MIN, f32::max);
+        assert_eq!(easing.overshoots(), peak > 1.0 + 1e-4, "{:?}", easing);
+        if !easing.overshoots() { assert!(samples.windows(2).all(|w| w[1] >= w[0]), "{:?}", easing); }
+    }
+}
+
+#[test]
+fn easings_decelerate() {
+    // Every curve covers more ground in its first tenth than in its last.
+    for easing in Easing::ALL {
+        let head = easing.apply(0.1) - easing.apply(0.0);
+        let tail = (easing.apply(1.0) - easing.apply(0.9)).abs();
+        assert!(head > tail, "{:?}", easing);
+    }
+}
+
+fn run(mut spin: SpinAnimation) -> f32 {
+    let mut position = 0.0;
+    for _ in 0..60 * 60 {
+        position = spin.advance(1.0 / 60.0);
+        if spin.phase() == SpinPhase::Done { return position; }
+    }
+    panic!("spin still in {:?} after a minute at {}", spin.phase(), position);
+}
+
+#[test]
+fn spins_end_exactly_on_the_target() {
+    for easing in Easing::ALL {
+        assert_eq!(run(SpinAnimation::new(0.0, 6000.0, 10.0, easing)), 6000.0);
+        assert_eq!(run(SpinAnimation::new(0.0, 6000.0, 10.0, easing).landing_offset(20.0).with_ticks(60.0)), 6000.0);
+        assert_eq!(run(SpinAnimation::new(0.0, 6000.0, 10.0, easing).with_nudge(60.0).with_ticks(60.0).with_near_miss(30.0)), 6000.0);
+    }
+}
+
+#[test]
+fn fast_forward_still_lands_on_the_target() {
+    let mut spin = SpinAnimation::new(0.0, 6000.0, 12.0, Easing::Power7).with_ticks(60.0);
+    for _ in 0..60 { spin.advance(1.0 / 60.0); }
+    spin.fast_forward(0.6);
+    let mut elapsed = 0.0;
+    while spin.phase() != SpinPhase::Done {
+        spin.advance(1.0 / 60.0);
+        elapsed += 1.0 / 60.0;
+        assert!(elapsed < 2.0, "fast-forwarded spin took too long");
+    }
+    assert_eq!(spin.progress(), 1.0);
+}
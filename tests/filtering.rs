@@ -0,0 +1,207 @@
+mod common;
+
+use common::server;
+use std::collections::{HashMap, HashSet};
+
+use squad_roulette::model::{
+    active_filter_chips, apply_filters, build_pool, count_exclusions, ChipKind, DeferredRebuild, FilterSettings,
+    PoolStats, Region, RouletteState, ServerItem, ServerLists, Settings,
+};
+
+#[test]
+fn default_filters_keep_busy_european_servers() {
+    let raw = vec![server("1", "DE", 80), server("2", "US", 80), server("3", "PL", 20), server("4", "UA", 100)];
+    assert_eq!(apply_filters(&raw, &FilterSettings::default()), vec![0, 3]);
+}
+
+#[test]
+fn player_bounds_are_inclusive() {
+    let raw = vec![server("1", "DE", 49), server("2", "DE", 50), server("3", "DE", 70), server("4", "DE", 71)];
+    let filters = FilterSettings { min_players: 50, max_players: 70, region: Region::Eu };
+    assert_eq!(apply_filters(&raw, &filters), vec![1, 2]);
+}
+
+#[test]
+fn regions_match_their_countries() {
+    assert!(Region::Eu.allows("UA"));
+    assert!(!Region::Eu.allows("US"));
+    assert!(Region::Na.allows("CA"));
+    assert!(!Region::Na.allows("GB"));
+    assert!(Region::All.allows("BR"));
+    assert!(Region::All.allows(""));
+}
+
+#[test]
+fn filtering_keeps_fetch_order() {
+    let raw: Vec<_> = (0..20).map(|i| server(&i.to_string(), if i % 3 == 0 { "US" } else { "FR" }, 60 + i)).collect();
+    let filters = FilterSettings { region: Region::All, ..FilterSettings::default() };
+    let kept = apply_filters(&raw, &filters);
+    assert_eq!(kept.len(), 20);
+    assert!(kept.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn exclusions_count_region_before_players() {
+    // The US server is too quiet as well, but it only counts against its region.
+    let raw = vec![server("1", "US", 10), server("2", "DE", 10), server("3", "DE", 90), server("4", "MX", 90)];
+    let filters = FilterSettings::default();
+    assert_eq!(count_exclusions(&raw, &filters), (2, 1));
+    let (by_region, by_players) = count_exclusions(&raw, &filters);
+    assert_eq!(apply_filters(&raw, &filters).len() + by_region + by_players, raw.len());
+}
+
+#[test]
+fn player_bounds_stay_ordered() {
+    let mut filters = FilterSettings { min_players: 90, max_players: 40, region: Region::Eu };
+    filters.enforce_player_bounds(true);
+    assert_eq!((filters.min_players, filters.max_players), (90, 90));
+
+    let mut filters = FilterSettings { min_players: 90, max_players: 40, region: Region::Eu };
+    filters.enforce_player_bounds(false);
+    assert_eq!((filters.min_players, filters.max_players), (40, 40));
+
+    let mut filters = FilterSettings { min_players: 150, max_players: 250, region: Region::Eu };
+    filters.enforce_player_bounds(true);
+    assert_eq!((filters.min_players, filters.max_players), (100, 100));
+}
+
+fn mixed_servers() -> Vec<ServerItem> {
+    let countries = ["DE", "US", "UA", "PL", "CA", "GB", "BR", ""];
+    let maps = ["Narva", "Yehorivka", "Gorodok", "Mutaha"];
+    (0..200u32)
+        .map(|i| {
+            let mut s = server(&i.to_string(), countries[(i * 7 % 8) as usize], i * 37 % 101);
+            s.map = maps[(i * 3 % 4) as usize].to_string();
+            s.max_players = 80 + i % 21;
+            s
+        })
+        .collect()
+}
+
+fn direct_tally(pool: &[&ServerItem], key: fn(&ServerItem) -> &str) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for s in pool { *counts.entry(key(s).to_string()).or_default() += 1; }
+    counts
+}
+
+#[test]
+fn pool_stats_agree_with_the_filters_they_summarize() {
+    let raw = mixed_servers();
+    for region in [Region::Eu, Region::Na, Region::All] {
+        for (min_players, max_players) in [(0, 100), (60, 100), (20, 40), (100, 100)] {
+            let filters = FilterSettings { min_players, max_players, region };
+            let kept: Vec<&ServerItem> = apply_filters(&raw, &filters).into_iter().map(|i| &raw[i]).collect();
+            let pool: Vec<ServerItem> = kept.iter().map(|&s| s.clone()).collect();
+            let stats = PoolStats::new(&pool);
+            let context = format!("{region:?} {min_players}..={max_players}");
+
+            assert_eq!(stats.servers, kept.len(), "{context}");
+            let (by_region, by_players) = count_exclusions(&raw, &filters);
+            assert_eq!(stats.servers + by_region + by_players, raw.len(), "{context}");
+            assert_eq!(stats.players, kept.iter().map(|s| s.players as u64).sum::<u64>(), "{context}");
+            assert_eq!(stats.capacity, kept.iter().map(|s| s.max_players as u64).sum::<u64>(), "{context}");
+
+            let countries = direct_tally(&kept, |s| &s.country);
+            let maps = direct_tally(&kept, |s| &s.map);
+            for (breakdown, direct) in [(&stats.countries, countries), (&stats.maps, maps)] {
+                let listed: HashMap<String, usize> = breakdown.iter().cloned().collect();
+                assert_eq!(listed.len(), breakdown.len(), "{context}: a value is listed twice");
+                assert_eq!(listed, direct, "{context}");
+                assert!(breakdown.windows(2).all(|w| w[0].1 > w[1].1 || (w[0].1 == w[1].1 && w[0].0 < w[1].0)), "{context}: {breakdown:?}");
+            }
+        }
+    }
+}
+
+fn with_filters(filters: FilterSettings) -> Settings {
+    let mut settings = Settings::default();
+    settings.filters = filters;
+    settings
+}
+
+fn chip_kinds(settings: &Settings) -> Vec<ChipKind> {
+    active_filter_chips(settings).into_iter().map(|chip| chip.kind).collect()
+}
+
+#[test]
+fn default_filters_show_no_chips() {
+    assert!(active_filter_chips(&Settings::default()).is_empty());
+}
+
+#[test]
+fn each_changed_filter_shows_one_chip() {
+    for region in [Region::Na, Region::All] {
+        let settings = with_filters(FilterSettings { region, ..FilterSettings::default() });
+        assert_eq!(chip_kinds(&settings), vec![ChipKind::Region], "{region:?}");
+    }
+    for (min_players, max_players) in [(0, 100), (60, 80), (10, 20)] {
+        let settings = with_filters(FilterSettings { min_players, max_players, ..FilterSettings::default() });
+        let chips = active_filter_chips(&settings);
+        assert_eq!(chips.len(), 1, "{min_players}..={max_players}");
+        assert_eq!(chips[0].kind, ChipKind::Players);
+        assert!(chips[0].label.contains(&format!("{min_players}–{max_players}")), "{}", chips[0].label);
+    }
+    let both = with_filters(FilterSettings { min_players: 5, max_players: 50, region: Region::All });
+    assert_eq!(chip_kinds(&both), vec![ChipKind::Region, ChipKind::Players]);
+}
+
+#[test]
+fn resetting_a_chip_clears_only_that_filter() {
+    let changed = FilterSettings { min_players: 5, max_players: 50, region: Region::Na };
+
+    let mut filters = changed.clone();
+    filters.reset_chip(ChipKind::Region);
+    assert_eq!(filters, FilterSettings { region: Region::Eu, ..changed.clone() });
+    assert_eq!(chip_kinds(&with_filters(filters)), vec![ChipKind::Players]);
+
+    let mut filters = changed.clone();
+    filters.reset_chip(ChipKind::Players);
+    assert_eq!(filters, FilterSettings { region: Region::Na, ..FilterSettings::default() });
+    assert_eq!(chip_kinds(&with_filters(filters.clone())), vec![ChipKind::Region]);
+
+    filters.reset_chip(ChipKind::Region);
+    assert_eq!(filters, FilterSettings::default());
+    assert!(active_filter_chips(&with_filters(filters)).is_empty());
+}
+
+
+fn filtered(raw: &[ServerItem], filters: &FilterSettings) -> Vec<ServerItem> {
+    apply_filters(raw, filters).into_iter().map(|i| raw[i].clone()).collect()
+}
+
+fn ids(pool: &[ServerItem]) -> Vec<&str> {
+    pool.iter().map(|s| s.id.as_str()).collect()
+}
+
+#[test]
+fn changes_made_mid_spin_reach_the_pool_once_it_stops() {
+    let raw = vec![server("a", "DE", 10), server("b", "DE", 30), server("c", "FR", 50)];
+    let mut filters = FilterSettings { min_players: 0, max_players: 100, region: Region::Eu };
+    let mut lists = ServerLists::default();
+    let excluded = HashSet::new();
+    let mut rebuild = DeferredRebuild::default();
+    let mut fetched = filtered(&raw, &filters);
+    let mut pool = build_pool(&fetched, &lists, &excluded);
+    assert_eq!(ids(&pool), ["a", "b", "c"]);
+
+    filters.min_players = 20;
+    fetched = filtered(&raw, &filters);
+    if rebuild.request(RouletteState::Spinning) { pool = build_pool(&fetched, &lists, &excluded); }
+    lists.add_to_blacklist(&raw[2]);
+    if rebuild.request(RouletteState::Spinning) { pool = build_pool(&fetched, &lists, &excluded); }
+    assert_eq!(ids(&pool), ["a", "b", "c"], "the wheel keeps its rows while it spins");
+
+    assert!(rebuild.settle());
+    pool = build_pool(&fetched, &lists, &excluded);
+    assert_eq!(ids(&pool), ["b"]);
+    assert!(!rebuild.settle(), "a settled rebuild isn't repeated by the next spin");
+}
+
+#[test]
+fn rebuilds_outside_a_spin_go_straight_through() {
+    let mut rebuild = DeferredRebuild::default();
+    for state in [RouletteState::Ready, RouletteState::Loading, RouletteState::Countdown, RouletteState::Finished] {
+        assert!(rebuild.request(state), "{state:?}");
+    }
+    assert!(!rebuild.settle());
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use common::server;
+use squad_roulette::format::{ago_bucket, escape_markdown, format_ago, format_thousands, pool_summary_text, render_discord_message};
+use squad_roulette::model::{unix_now, Language, PoolStats};
+
+#[test]
+fn thousands_are_grouped_per_language() {
+    assert_eq!(format_thousands(0, Language::En), "0");
+    assert_eq!(format_thousands(999, Language::En), "999");
+    assert_eq!(format_thousands(1000, Language::En), "1,000");
+    assert_eq!(format_thousands(1234567, Language::En), "1,234,567");
+    assert_eq!(format_thousands(1234567, Language::Uk), "1\u{202F}234\u{202F}567");
+}
+
+#[test]
+fn ages_round_down_to_the_unit() {
+    let now = unix_now();
+    assert_eq!(format_ago(now, Language::En), "just now");
+    assert_eq!(format_ago(now - 61, Language::En), "1 min ago");
+    assert_eq!(format_ago(now - 3599, Language::En), "59 min ago");
+    assert_eq!(format_ago(now - 7300, Language::En), "2 h ago");
+    // A clock that went backwards reads as now, not as a huge age.
+    assert_eq!(format_ago(now + 500, Language::En), "just now");
+}
+
+#[test]
+fn age_buckets_follow_the_shown_unit() {
+    let now = unix_now();
+    assert_eq!(ago_bucket(now - 30), 0);
+    assert_eq!(ago_bucket(now - 130), 120);
+    assert_eq!(ago_bucket(now - 7300), 7200);
+    assert_eq!(ago_bucket(now + 500), 0);
+}
+
+#[test]
+fn markdown_is_escaped() {
+    assert_eq!(escape_markdown("plain name"), "plain name");
+    assert_eq!(escape_markdown("**[EU] _Dad's_ ~Army~**"), "\\*\\*\\[EU\\] \\_Dad's\\_ \\~Army\\~\\*\\*");
+    assert_eq!(escape_markdown("a|b`c>d\\"), "a\\|b\\`c\\>d\\\\");
+}
+
+#[test]
+fn discord_templates_fill_known_placeholders() {
+    let mut server = server("42", "DE", 98);
+    server.name = "*Best* server".to_string();
+    server.queue = 3;
+    let text = render_discord_message("{name} on {map} ({mode}) {players} <{link}> {unknown} {", &server);
+    assert_eq!(text, "\\*Best\\* server on Narva (RAAS) 98/100 (+3) <https://www.battlemetrics.com/servers/squad/42> {unknown} {");
+}
+
+#[test]
+fn pool_summaries_total_the_pool() {
+    assert_eq!(pool_summary_text(&PoolStats::new(&[]), Language::En), "");
+    let servers = vec![server("1", "DE", 90), server("2", "PL", 70), server("3", "DE", 1500)];
+    assert_eq!(pool_summary_text(&PoolStats::new(&servers), Language::En), "3 servers · 1,660 players online · avg 553/100");
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::server;
+use squad_roulette::model::{trim_history_snapshots, AnimationSettings, HistoryEntry, PoolStats, ServerPool, SpinRecord};
+use squad_roulette::selection::choose_outcome;
+
+// Every third pick is manual and has no spin to keep, like a winner chosen from the table.
+fn history(len: usize) -> Vec<HistoryEntry> {
+    let pool: ServerPool = (0..8).map(|i| server(&i.to_string(), "DE", 80)).collect();
+    let stats = PoolStats::new(&pool);
+    (0..len)
+        .map(|i| {
+            let manual = i % 3 == 2;
+            let spin = (!manual).then(|| SpinRecord { outcome: choose_outcome(i as u64, &stats, 0.0, &AnimationSettings::default()), pool: pool.clone() });
+            HistoryEntry::new(server(&format!("winner {i}"), "DE", 80), manual, spin)
+        })
+        .collect()
+}
+
+fn replayable(history: &[HistoryEntry]) -> Vec<usize> {
+    history.iter().enumerate().filter(|(_, e)| e.spin.is_some()).map(|(i, _)| i).collect()
+}
+
+#[test]
+fn trimming_keeps_only_the_newest_spins_replayable() {
+    let mut entries = history(30);
+    trim_history_snapshots(&mut entries, 5);
+    assert_eq!(replayable(&entries), vec![22, 24, 25, 27, 28]);
+    // Trimmed entries still remember who won.
+    assert_eq!(entries.len(), 30);
+    assert!(entries.iter().enumerate().all(|(i, e)| e.server.id == format!("winner {i}")));
+    assert_eq!(entries.iter().filter(|e| e.manual).count(), 10);
+}
+
+#[test]
+fn trimming_under_the_cap_changes_nothing() {
+    let mut entries = history(6);
+    let before = replayable(&entries);
+    trim_history_snapshots(&mut entries, 20);
+    assert_eq!(replayable(&entries), before);
+    trim_history_snapshots(&mut entries, before.len());
+    assert_eq!(replayable(&entries), before);
+}
+
+#[test]
+fn trimming_to_zero_forgets_every_spin() {
+    let mut entries = history(10);
+    trim_history_snapshots(&mut entries, 0);
+    assert!(replayable(&entries).is_empty());
+    assert_eq!(entries.len(), 10);
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use common::server;
+use squad_roulette::animation::{
+    plan_spin, row_at, rows_crossed, server_at, spin_rows, visible_rows, Easing, SpinAnimation, SpinPhase,
+};
+use squad_roulette::model::{AnimationSettings, PoolStats, RowSize, WheelMode};
+use squad_roulette::selection::{choose_outcome, crossfade_names, pick_winner};
+
+#[test]
+fn rows_wrap_around_the_pool() {
+    assert_eq!(server_at(0, 5), 0);
+    assert_eq!(server_at(7, 5), 2);
+    assert_eq!(server_at(-1, 5), 4);
+    assert_eq!(server_at(-10, 5), 0);
+    // An empty pool has nothing to show, but asking mustn't divide by zero.
+    assert_eq!(server_at(3, 0), 0);
+}
+
+#[test]
+fn pointer_rounds_to_the_nearest_row() {
+    assert_eq!(row_at(0.0, 60.0), 0);
+    assert_eq!(row_at(29.0, 60.0), 0);
+    assert_eq!(row_at(31.0, 60.0), 1);
+    assert_eq!(row_at(-31.0, 60.0), -1);
+    assert_eq!(row_at(600.0, 60.0), 10);
+}
+
+#[test]
+fn visible_rows_include_partly_shown_ones() {
+    let (view, pitch) = (200.0, 60.0);
+    for scroll in [0.0, 29.9, 30.0, 1234.5, -777.0] {
+        let rows = visible_rows(scroll, view, pitch);
+        for row in -50..50 {
+            let centre = row as f32 * pitch;
+            let shown = centre + pitch / 2.0 > scroll - view / 2.0 && centre - pitch / 2.0 < scroll + view / 2.0;
+            if shown { assert!(rows.contains(&row), "row {} missing at scroll {}", row, scroll); }
+        }
+        assert!(rows.contains(&row_at(scroll, pitch)));
+        assert!(((rows.end - rows.start) as f32) <= view / pitch + 3.0);
+    }
+}
+
+#[test]
+fn row_crossings_count_boundaries_either_way() {
+    assert_eq!(rows_crossed(0.0, 29.0, 60.0), 0);
+    assert_eq!(rows_crossed(0.0, 31.0, 60.0), 1);
+    assert_eq!(rows_crossed(0.0, 600.0, 60.0), 10);
+    assert_eq!(rows_crossed(600.0, 0.0, 60.0), 10);
+}
+
+fn pool(len: usize) -> PoolStats {
+    let servers: Vec<_> = (0..len).map(|i| server(&i.to_string(), "DE", 80)).collect();
+    PoolStats::new(&servers)
+}
+
+#[test]
+fn the_same_seed_gives_the_same_spin() {
+    let stats = pool(40);
+    let animation = AnimationSettings::default();
+    for seed in [0, 1, 42, u64::MAX] {
+        assert_eq!(choose_outcome(seed, &stats, 3.0, &animation), choose_outcome(seed, &stats, 3.0, &animation));
+    }
+}
+
+#[test]
+fn outcomes_stay_inside_the_settings() {
+    let stats = pool(7);
+    let animation = AnimationSettings::default();
+    for seed in 0..500 {
+        let outcome = choose_outcome(seed, &stats, 0.0, &animation);
+        assert!(outcome.winner_idx < 7);
+        assert!((animation.min_duration..=animation.max_duration).contains(&outcome.duration));
+        // The wheel must come to rest with the pointer over the winner's own row.
+        assert!(outcome.offset.abs() < 0.5);
+        assert!(!outcome.nudge && !outcome.near_miss);
+    }
+}
+
+#[test]
+fn quick_instant_and_animated_spins_agree_on_the_winner() {
+    let stats = pool(23);
+    let instant = AnimationSettings { instant: true, ..AnimationSettings::default() };
+    let animated = AnimationSettings { easing: Easing::BackOut, near_miss: true, nudge: true, nudge_percent: 100, ..AnimationSettings::default() };
+    let reduced = AnimationSettings { reduced_motion: true, ..AnimationSettings::default() };
+    for seed in [0, 7, 1234, u64::MAX] {
+        let quick = pick_winner(seed, &stats);
+        assert_eq!(choose_outcome(seed, &stats, 0.0, &instant).winner_idx, quick, "seed {seed}");
+        assert_eq!(choose_outcome(seed, &stats, 11.0, &animated).winner_idx, quick, "seed {seed}");
+        assert_eq!(choose_outcome(seed, &stats, -4.5, &reduced).winner_idx, quick, "seed {seed}");
+    }
+}
+
+fn final_scroll(mut spin: SpinAnimation) -> f32 {
+    for _ in 0..60 * 60 {
+        let scroll = spin.advance(1.0 / 60.0);
+        if spin.phase() == SpinPhase::Done { return scroll; }
+    }
+    panic!("spin still in {:?} after a minute", spin.phase());
+}
+
+#[test]
+fn animated_spins_come_to_rest_on_the_winner() {
+    let stats = pool(37);
+    let pitch = 60.0;
+    for seed in 0..300 {
+        let animation = AnimationSettings {
+            easing: Easing::ALL[seed as usize % Easing::ALL.len()],
+            near_miss: seed % 3 == 0,
+            nudge: true,
+            nudge_percent: 50,
+            ..AnimationSettings::default()
+        };
+        let outcome = choose_outcome(seed, &stats, (seed % 11) as f32 - 5.0, &animation);
+        let scroll = final_scroll(plan_spin(&outcome, 37, pitch));
+        assert_eq!(server_at(row_at(scroll, pitch), 37), outcome.winner_idx, "seed {seed}");
+    }
+}
+
+#[test]
+fn spins_land_on_the_winner_in_every_layout() {
+    let animation = AnimationSettings { near_miss: true, nudge: true, nudge_percent: 50, ..AnimationSettings::default() };
+    for size in RowSize::ALL {
+        for mode in WheelMode::ALL {
+            // The zoom factor only changes how many pixels a point is, so a scaled pitch must land the same.
+            for ui_scale in [1.0, 1.35] {
+                let pitch = mode.pitch(size.height()) * ui_scale;
+                for len in [1, 2, 3, 37] {
+                    let stats = pool(len);
+                    for seed in 0..6 {
+                        // Each spin starts where the one before it stopped, as in the app.
+                        let mut slot = 0.0;
+                        for spin in 0..3 {
+                            let outcome = choose_outcome(seed * 3 + spin, &stats, slot, &animation);
+                            let scroll = final_scroll(plan_spin(&outcome, len, pitch));
+                            let context = format!("{size:?} {mode:?} x{ui_scale} pool {len} seed {seed} spin {spin}");
+                            assert_eq!(server_at(row_at(scroll, pitch), len), outcome.winner_idx, "{context}");
+                            slot = scroll / pitch;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn seeds_keep_picking_the_winners_they_always_have() {
+    // Pinned from the draw as it was when the pool still carried per-server weights.
+    let picks: Vec<usize> = [0, 1, 42, 9001].iter().map(|&seed| pick_winner(seed, &pool(50))).collect();
+    assert_eq!(picks, [36, 48, 26, 36]);
+}
+
+#[test]
+fn every_server_can_win() {
+    let stats = pool(5);
+    let animation = AnimationSettings::default();
+    let mut won = [false; 5];
+    for seed in 0..200 { won[choose_outcome(seed, &stats, 0.0, &animation).winner_idx] = true; }
+    assert!(won.iter().all(|&w| w));
+}
+
+#[test]
+fn crossfades_end_on_the_winner() {
+    let stats = pool(6);
+    let outcome = choose_outcome(9, &stats, 0.0, &AnimationSettings::default());
+    let names = crossfade_names(&outcome, 6);
+    assert_eq!(names.last(), Some(&outcome.winner_idx));
+    assert!(names.windows(2).all(|w| w[0] != w[1]));
+    assert!(names.iter().all(|&i| i < 6));
+}
+
+#[test]
+fn crossfades_never_repeat_a_name_in_small_pools() {
+    for len in [2, 3] {
+        let stats = pool(len);
+        for seed in 0..200 {
+            let outcome = choose_outcome(seed, &stats, 0.0, &AnimationSettings::default());
+            let names = crossfade_names(&outcome, len);
+            assert_eq!(names.len(), 10, "pool {len}, seed {seed}");
+            assert_eq!(names.last(), Some(&outcome.winner_idx), "pool {len}, seed {seed}");
+            assert!(names.windows(2).all(|w| w[0] != w[1]), "pool {len}, seed {seed}: {names:?}");
+            assert!(names.iter().all(|&i| i < len), "pool {len}, seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn a_single_server_crossfades_straight_to_itself() {
+    let outcome = choose_outcome(3, &pool(1), 0.0, &AnimationSettings::default());
+    assert_eq!(crossfade_names(&outcome, 1), vec![0]);
+}
+
+#[test]
+fn longer_spins_scroll_further() {
+    assert!(spin_rows(20.0) > spin_rows(10.0));
+    assert_eq!(spin_rows(0.0), 10);
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use common::server;
+use squad_roulette::model::ServerItem;
+use squad_roulette::snapshot::{decode, encode};
+
+fn servers() -> Vec<ServerItem> {
+    let mut servers = vec![server("1", "DE", 80), server("2", "UA", 0), server("3", "", 100)];
+    servers[0].name = "🔥 [EU] Squad Roulette | 🇪🇺 Fresh maps 🎲".to_string();
+    servers[1].name = "Українська спільнота Squad — Київ".to_string();
+    servers[1].map = "Гусь-Хрустальний".to_string();
+    servers[1].rank = Some(7);
+    servers[1].licensed = false;
+    servers[2].name = String::new();
+    servers[2].ip = "203.0.113.9".to_string();
+    servers[2].port = 7787;
+    servers[2].queue = u32::MAX;
+    servers
+}
+
+#[test]
+fn snapshots_round_trip_every_field() {
+    let servers = servers();
+    let snapshot = decode(&encode(&servers, 1_760_000_000)).unwrap();
+    assert_eq!(snapshot.saved_at, 1_760_000_000);
+    assert_eq!(snapshot.servers, servers);
+}
+
+#[test]
+fn an_empty_list_round_trips() {
+    let snapshot = decode(&encode(&[], 0)).unwrap();
+    assert!(snapshot.servers.is_empty());
+}
+
+#[test]
+fn any_flipped_byte_is_rejected() {
+    let bytes = encode(&servers(), 42);
+    for i in 0..bytes.len() {
+        let mut damaged = bytes.clone();
+        damaged[i] ^= 0x20;
+        assert!(decode(&damaged).is_err(), "flipped byte {i} went unnoticed");
+    }
+}
+
+#[test]
+fn truncated_snapshots_are_rejected() {
+    let bytes = encode(&servers(), 42);
+    for len in 0..bytes.len() {
+        assert!(decode(&bytes[..len]).is_err(), "a {len}-byte prefix decoded");
+    }
+}
+
+// Damage that still carries a valid checksum, as a file from another program or a future version would.
+fn with_checksum(mut body: Vec<u8>) -> Vec<u8> {
+    let checksum = crc32fast::hash(&body);
+    body.extend_from_slice(&checksum.to_le_bytes());
+    body
+}
+
+#[test]
+fn well_formed_but_foreign_files_are_rejected() {
+    let bytes = encode(&servers(), 42);
+    let body = bytes[..bytes.len() - 4].to_vec();
+
+    let mut magic = body.clone();
+    magic[..4].copy_from_slice(b"JSON");
+    assert_eq!(decode(&with_checksum(magic)).err().as_deref(), Some("not a server snapshot"));
+
+    let mut version = body.clone();
+    version[4] = 2;
+    assert_eq!(decode(&with_checksum(version)).err().as_deref(), Some("unsupported snapshot version 2"));
+
+    let mut trailing = body.clone();
+    trailing.push(0);
+    assert!(decode(&with_checksum(trailing)).is_err());
+
+    // A count far beyond the data must fail on the short read, not try to allocate for it.
+    let mut count = body;
+    count[13..17].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(decode(&with_checksum(count)).is_err());
+}
@@ -0,0 +1,74 @@
+use squad_roulette::model::{refresh_blocker, spin_blocker, Language, RouletteState, SpinBlocker};
+
+const ALL_STATES: [RouletteState; 5] = [RouletteState::Ready, RouletteState::Loading, RouletteState::Countdown, RouletteState::Spinning, RouletteState::Finished];
+
+#[test]
+fn an_idle_wheel_with_fresh_servers_can_spin() {
+    assert_eq!(spin_blocker(RouletteState::Ready, false, false, 12, None), None);
+    assert_eq!(spin_blocker(RouletteState::Finished, false, false, 1, None), None);
+}
+
+#[test]
+fn loading_blocks_the_spin() {
+    assert_eq!(spin_blocker(RouletteState::Loading, false, false, 12, None), Some(SpinBlocker::Loading));
+    // An empty, stale pool is expected while loading; the load is the reason to give.
+    assert_eq!(spin_blocker(RouletteState::Loading, false, true, 0, None), Some(SpinBlocker::Loading));
+}
+
+#[test]
+fn a_countdown_or_running_spin_blocks_another() {
+    for state in [RouletteState::Countdown, RouletteState::Spinning] {
+        assert_eq!(spin_blocker(state, false, false, 12, None), Some(SpinBlocker::Spinning), "{state:?}");
+        assert_eq!(spin_blocker(state, false, true, 0, None), Some(SpinBlocker::Spinning), "{state:?}");
+    }
+}
+
+#[test]
+fn a_paused_spin_says_how_to_resume() {
+    assert_eq!(spin_blocker(RouletteState::Spinning, true, false, 12, None), Some(SpinBlocker::Paused));
+    // Pausing only means something mid-spin.
+    assert_eq!(spin_blocker(RouletteState::Finished, true, false, 12, None), None);
+}
+
+#[test]
+fn stale_data_blocks_the_spin() {
+    for state in [RouletteState::Ready, RouletteState::Finished] {
+        assert_eq!(spin_blocker(state, false, true, 12, None), Some(SpinBlocker::Stale), "{state:?}");
+        assert_eq!(spin_blocker(state, false, true, 0, None), Some(SpinBlocker::Stale), "{state:?}");
+    }
+}
+
+#[test]
+fn an_empty_pool_blocks_the_spin() {
+    assert_eq!(spin_blocker(RouletteState::Ready, false, false, 0, None), Some(SpinBlocker::EmptyPool));
+}
+
+#[test]
+fn a_replay_is_judged_by_the_live_pool() {
+    // The replayed spin's pool is on the wheel, but a new spin leaves the replay for the live one.
+    assert_eq!(spin_blocker(RouletteState::Finished, false, false, 30, Some(0)), Some(SpinBlocker::EmptyPool));
+    assert_eq!(spin_blocker(RouletteState::Finished, false, false, 0, Some(30)), None);
+    assert_eq!(spin_blocker(RouletteState::Spinning, false, false, 30, Some(30)), Some(SpinBlocker::Spinning));
+}
+
+#[test]
+fn refreshes_wait_for_the_same_states_as_spins() {
+    for state in ALL_STATES {
+        let expected = match state {
+            RouletteState::Loading => Some(SpinBlocker::Loading),
+            RouletteState::Countdown | RouletteState::Spinning => Some(SpinBlocker::Spinning),
+            RouletteState::Ready | RouletteState::Finished => None,
+        };
+        assert_eq!(refresh_blocker(state), expected, "{state:?}");
+    }
+}
+
+#[test]
+fn every_blocker_explains_itself_in_both_languages() {
+    let all = [SpinBlocker::Loading, SpinBlocker::Spinning, SpinBlocker::Paused, SpinBlocker::Stale, SpinBlocker::EmptyPool];
+    for lang in [Language::Uk, Language::En] {
+        let reasons: Vec<&str> = all.iter().map(|b| b.reason(lang)).collect();
+        assert!(reasons.iter().all(|r| !r.is_empty()), "{lang:?}");
+        assert!(reasons.iter().enumerate().all(|(i, r)| !reasons[..i].contains(r)), "{lang:?}: {reasons:?}");
+    }
+}
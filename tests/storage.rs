@@ -0,0 +1,184 @@
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use squad_roulette::model::Settings;
+use squad_roulette::storage::{load_json_or_default, load_settings_from, prune_aside_files, write_atomic};
+
+fn aside_files(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir).unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .filter(|name| name.starts_with("settings.corrupt-"))
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn a_missing_file_gives_defaults_and_touches_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let settings: Settings = load_json_or_default(&dir.path().join("settings.json"));
+    assert_eq!(settings, Settings::default());
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn unreadable_settings_are_moved_aside_for_defaults() {
+    let full = serde_json::to_vec_pretty(&Settings::default()).unwrap();
+    for bytes in [&full[..full.len() / 2], b"\x00\xffnot json at all".as_slice(), b"".as_slice()] {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        std::fs::write(&path, bytes).unwrap();
+        let settings: Settings = load_json_or_default(&path);
+        assert_eq!(settings, Settings::default());
+        assert!(!path.exists());
+        // The broken file is kept, byte for byte, for the user to look at.
+        let aside = aside_files(dir.path());
+        assert_eq!(aside.len(), 1, "{aside:?}");
+        assert_eq!(std::fs::read(dir.path().join(&aside[0])).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn writes_replace_the_whole_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("nested").join("lists.json");
+    write_atomic(&path, b"a much longer first version").unwrap();
+    write_atomic(&path, b"short").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"short");
+    assert!(!dir.path().join("nested").join(".lists.json.tmp").exists());
+}
+
+#[test]
+fn an_interrupted_write_leaves_the_old_file_intact() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("settings.json");
+    write_atomic(&path, b"{\"old\": true}").unwrap();
+    // A directory where the temporary file goes makes the write fail before anything is renamed.
+    let tmp = dir.path().join(".settings.json.tmp");
+    std::fs::create_dir(&tmp).unwrap();
+    assert!(write_atomic(&path, b"{\"new\": true}").is_err());
+    assert_eq!(std::fs::read(&path).unwrap(), b"{\"old\": true}");
+
+    // A crash mid-write leaves a half-written temporary file behind; the real one is still whole,
+    // and the next write simply replaces the leftover.
+    std::fs::remove_dir(&tmp).unwrap();
+    std::fs::write(&tmp, b"{\"new\": tr").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"{\"old\": true}");
+    write_atomic(&path, b"{\"new\": true}").unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"{\"new\": true}");
+    assert!(!tmp.exists());
+}
+
+fn load_fixture(name: &str) -> (tempfile::TempDir, Settings, Option<String>) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("settings.json");
+    std::fs::copy(Path::new("tests/fixtures").join(name), &path).unwrap();
+    let (settings, warning) = load_settings_from(&path);
+    (dir, settings, warning)
+}
+
+#[test]
+fn v1_settings_migrate_and_keep_their_values() {
+    let (_dir, settings, warning) = load_fixture("settings_v1.json");
+    let mut expected = Settings::default();
+    expected.filters.min_players = 40;
+    expected.filters.max_players = 90;
+    expected.audio.sound_enabled = false;
+    // Anyone upgrading already chose their filters, so they skip the first-run questions.
+    expected.onboarding_done = true;
+    assert_eq!(settings, expected);
+    assert_eq!(warning, None);
+}
+
+#[test]
+fn v2_settings_migrate_and_keep_their_values() {
+    let (_dir, settings, warning) = load_fixture("settings_v2.json");
+    let mut expected = Settings::default();
+    expected.filters.min_players = 30;
+    expected.filters.max_players = 80;
+    expected.onboarding_done = true;
+    assert_eq!(settings, expected);
+    assert_eq!(warning, None);
+}
+
+#[test]
+fn current_settings_load_unchanged() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("settings.json");
+    let mut saved = Settings::default();
+    saved.onboarding_done = true;
+    saved.filters.min_players = 10;
+    saved.audio.volume = 35;
+    std::fs::write(&path, serde_json::to_vec(&saved).unwrap()).unwrap();
+    assert_eq!(load_settings_from(&path), (saved, None));
+}
+
+#[test]
+fn settings_from_a_newer_version_are_backed_up_not_overwritten() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("settings.json");
+    let future = br#"{"config_version": 99, "filters": {"min_players": 1, "max_players": 2}, "hologram": true}"#;
+    std::fs::write(&path, future).unwrap();
+    let (settings, warning) = load_settings_from(&path);
+    assert_eq!(settings, Settings::default());
+    let warning = warning.expect("the user is told about the backup");
+    assert!(warning.contains("v99") && warning.contains("settings.v99.json"), "{warning}");
+    // The newer file survives byte for byte, out of the way of the defaults the app will save.
+    assert!(!path.exists());
+    assert_eq!(std::fs::read(dir.path().join("settings.v99.json")).unwrap(), future);
+}
+
+#[test]
+fn settings_that_no_longer_fit_are_moved_aside() {
+    for bytes in [br#"{"config_version": 3, "filters": {"min_players": "lots"}}"#.as_slice(), b"[1, 2, 3]".as_slice(), b"42".as_slice()] {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        std::fs::write(&path, bytes).unwrap();
+        assert_eq!(load_settings_from(&path), (Settings::default(), None));
+        let aside = aside_files(dir.path());
+        assert_eq!(aside.len(), 1, "{aside:?}");
+        assert_eq!(std::fs::read(dir.path().join(&aside[0])).unwrap(), bytes);
+    }
+}
+
+fn age(path: &Path, minutes: u64) {
+    let file = std::fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - Duration::from_secs(60 * minutes)).unwrap();
+}
+
+#[test]
+fn pruning_keeps_the_newest_set_aside_files_under_the_cap() {
+    let dir = tempfile::tempdir().unwrap();
+    let aside = ["settings.corrupt-1.json", "lists.corrupt-2.json", "settings.v4.json", "settings.corrupt-3.json", "settings.v5.json"];
+    for (i, name) in aside.iter().enumerate() {
+        let path = dir.path().join(name);
+        std::fs::write(&path, [b'x'; 100]).unwrap();
+        // The list runs newest first.
+        age(&path, i as u64 * 10);
+    }
+    // Everything else in the data directory is left alone, however large or old.
+    for name in ["settings.json", "lists.json", "servers.bin", "notes.corrupt.txt"] {
+        let path = dir.path().join(name);
+        std::fs::write(&path, [b'y'; 1000]).unwrap();
+        age(&path, 600);
+    }
+
+    prune_aside_files(dir.path(), 250);
+    let exists = |name: &str| dir.path().join(name).exists();
+    assert!(exists(aside[0]) && exists(aside[1]));
+    assert!(!exists(aside[2]) && !exists(aside[3]) && !exists(aside[4]));
+    assert!(["settings.json", "lists.json", "servers.bin", "notes.corrupt.txt"].iter().all(|name| exists(name)));
+
+    // Under the cap nothing more goes.
+    prune_aside_files(dir.path(), 250);
+    assert!(exists(aside[0]) && exists(aside[1]));
+    prune_aside_files(dir.path(), 0);
+    assert!(!exists(aside[0]) && !exists(aside[1]));
+}
+
+#[test]
+fn pruning_a_missing_directory_does_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    prune_aside_files(&dir.path().join("gone"), 0);
+}
+
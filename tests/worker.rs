@@ -0,0 +1,96 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use squad_roulette::worker::{Generation, Worker};
+
+// Keeps the worker busy until the returned sender is used or dropped, so the tests can line up a queue.
+fn block(worker: &Worker, generation: &Generation) -> (Sender<()>, Receiver<()>) {
+    let (release_tx, release_rx) = channel::<()>();
+    let (started_tx, started_rx) = channel();
+    assert!(worker.submit(generation.advance(), move |_| {
+        let _ = started_tx.send(());
+        let _ = release_rx.recv();
+    }));
+    started_rx.recv_timeout(Duration::from_secs(5)).expect("the blocking job starts");
+    let (done_tx, done_rx) = channel();
+    assert!(worker.submit(generation.advance(), move |_| { let _ = done_tx.send(()); }));
+    (release_tx, done_rx)
+}
+
+#[test]
+fn queued_jobs_from_an_older_generation_are_skipped() {
+    let worker = Worker::new();
+    let fetches = Generation::default();
+    let (release, _) = block(&worker, &Generation::default());
+    let ran = Arc::new(AtomicBool::new(false));
+    let stale_ran = ran.clone();
+    assert!(worker.submit(fetches.advance(), move |_| stale_ran.store(true, Ordering::SeqCst)));
+    let (done_tx, done_rx) = channel();
+    assert!(worker.submit(fetches.advance(), move |ticket| { let _ = done_tx.send(ticket.is_current()); }));
+    release.send(()).unwrap();
+    assert_eq!(done_rx.recv_timeout(Duration::from_secs(5)), Ok(true));
+    assert!(!ran.load(Ordering::SeqCst));
+}
+
+#[test]
+fn a_running_job_sees_its_ticket_go_stale() {
+    let worker = Worker::new();
+    let generation = Generation::default();
+    let (check_tx, check_rx) = channel::<()>();
+    let (seen_tx, seen_rx) = channel();
+    assert!(worker.submit(generation.advance(), move |ticket| {
+        let _ = seen_tx.send(ticket.is_current());
+        let _ = check_rx.recv();
+        let _ = seen_tx.send(ticket.is_current());
+    }));
+    assert_eq!(seen_rx.recv_timeout(Duration::from_secs(5)), Ok(true));
+    let _newer = generation.advance();
+    check_tx.send(()).unwrap();
+    assert_eq!(seen_rx.recv_timeout(Duration::from_secs(5)), Ok(false));
+}
+
+#[test]
+fn other_generations_stay_current() {
+    let fetches = Generation::default();
+    let saves = Generation::default();
+    let fetch = fetches.advance();
+    let _save = saves.advance();
+    assert!(fetch.is_current());
+}
+
+#[test]
+fn shutdown_drops_queued_jobs_and_refuses_new_ones() {
+    let mut worker = Worker::new();
+    let (release, done) = block(&worker, &Generation::default());
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        let _ = release.send(());
+    });
+    let started = Instant::now();
+    worker.shutdown();
+    assert!(started.elapsed() < Duration::from_secs(2), "waited {:?} for a job that finished", started.elapsed());
+    assert!(done.try_recv().is_err());
+    assert!(!worker.submit(Generation::default().advance(), |_| {}));
+}
+
+#[test]
+fn shutdown_gives_up_on_a_stuck_job() {
+    let mut worker = Worker::new();
+    let (release, _) = block(&worker, &Generation::default());
+    let started = Instant::now();
+    worker.shutdown();
+    let waited = started.elapsed();
+    assert!(waited >= Duration::from_millis(1900) && waited < Duration::from_secs(4), "waited {:?}", waited);
+    drop(release);
+}
+
+#[test]
+fn an_idle_worker_shuts_down_at_once() {
+    let mut worker = Worker::new();
+    let started = Instant::now();
+    worker.shutdown();
+    worker.shutdown();
+    assert!(started.elapsed() < Duration::from_millis(500));
+}